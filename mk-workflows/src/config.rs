@@ -48,21 +48,21 @@ pub fn qa_jobs() -> Vec<Job> {
         Job {
             name: "stable-all-features",
             toolchain: "stable",
-            features: "gl,vulkan,textlayout,webp".into(),
+            features: "gl,vulkan,textlayout,webp,svg,animation,particles".into(),
             example_args: Some("--driver cpu --driver pdf --driver svg".into()),
             ..Job::default()
         },
         Job {
             name: "stable-all-features-debug",
             toolchain: "stable",
-            features: "gl,vulkan,textlayout,webp".into(),
+            features: "gl,vulkan,textlayout,webp,svg,animation,particles".into(),
             skia_debug: true,
             ..Job::default()
         },
         Job {
             name: "beta-all-features",
             toolchain: "beta",
-            features: "gl,vulkan,textlayout,webp".into(),
+            features: "gl,vulkan,textlayout,webp,svg,animation,particles".into(),
             ..Job::default()
         },
     ]
@@ -10,6 +10,11 @@ pub mod lib {
     pub const SKIA_BINDINGS: &str = "skia-bindings";
     pub const SK_SHAPER: &str = "skshaper";
     pub const SK_PARAGRAPH: &str = "skparagraph";
+    pub const SVG: &str = "svg";
+    pub const SKOTTIE: &str = "skottie";
+    pub const SK_SG: &str = "sksg";
+    pub const PARTICLES: &str = "particles";
+    pub const SK_RESOURCES: &str = "skresources";
 }
 
 /// The configuration of the resulting binaries.
@@ -52,11 +57,33 @@ impl BinariesConfiguration {
         let mut additional_files = Vec::new();
         let feature_ids = features.ids();
 
+        // On Linux, the order of the libraries is significant. Libraries that depend on others
+        // must be pushed first.
+        if features.svg {
+            ninja_built_libraries.push(lib::SVG.into());
+        }
+
+        if features.animation {
+            ninja_built_libraries.push(lib::SKOTTIE.into());
+            ninja_built_libraries.push(lib::SK_SG.into());
+        }
+
+        if features.particles {
+            ninja_built_libraries.push(lib::PARTICLES.into());
+        }
+
+        if features.skresources() {
+            ninja_built_libraries.push(lib::SK_RESOURCES.into());
+        }
+
         if features.text_layout {
             if target.is_windows() {
                 additional_files.push(ICUDTL_DAT.into());
             }
             ninja_built_libraries.push(lib::SK_PARAGRAPH.into());
+        }
+
+        if features.skshaper() {
             ninja_built_libraries.push(lib::SK_SHAPER.into());
         }
 
@@ -30,13 +30,16 @@ pub struct Features {
     /// Support the decoding of the WEBP image format to bitmap data.
     pub webp_decode: bool,
 
-    /// Build with animation support (yet unsupported, no wrappers).
+    /// Build the SVG module that loads and renders SVG documents.
+    pub svg: bool,
+
+    /// Build the Skottie module that plays back Lottie animations.
     pub animation: bool,
 
     /// Support DNG file format (currently unsupported because of build errors).
     pub dng: bool,
 
-    /// Build the particles module (unsupported, no wrappers).
+    /// Build the particles module.
     pub particles: bool,
 }
 
@@ -54,9 +57,10 @@ impl Default for Features {
             text_layout: cfg!(feature = "textlayout"),
             webp_encode: cfg!(feature = "webp-encode"),
             webp_decode: cfg!(feature = "webp-decode"),
-            animation: false,
+            svg: cfg!(feature = "svg"),
+            animation: cfg!(feature = "animation"),
             dng: false,
-            particles: false,
+            particles: cfg!(feature = "particles"),
         }
     }
 }
@@ -66,6 +70,17 @@ impl Features {
         self.gl || self.vulkan || self.metal || self.d3d
     }
 
+    /// Is the skshaper module needed? It is used for text shaping by skparagraph, svg, and skottie.
+    pub fn skshaper(&self) -> bool {
+        self.text_layout || self.svg || self.animation
+    }
+
+    /// Is the skresources module needed? It loads external resources for svg, skottie, and
+    /// particles.
+    pub fn skresources(&self) -> bool {
+        self.svg || self.animation || self.particles
+    }
+
     /// Feature Ids used to look up prebuilt binaries.
     pub fn ids(&self) -> Vec<&str> {
         let mut feature_ids = Vec::new();
@@ -100,6 +115,15 @@ impl Features {
         if self.webp_decode {
             feature_ids.push(feature_id::WEBPD);
         }
+        if self.svg {
+            feature_ids.push(feature_id::SVG);
+        }
+        if self.animation {
+            feature_ids.push(feature_id::ANIMATION);
+        }
+        if self.particles {
+            feature_ids.push(feature_id::PARTICLES);
+        }
 
         feature_ids
     }
@@ -114,6 +138,9 @@ mod feature_id {
     pub const TEXTLAYOUT: &str = "textlayout";
     pub const WEBPE: &str = "webpe";
     pub const WEBPD: &str = "webpd";
+    pub const SVG: &str = "svg";
+    pub const ANIMATION: &str = "animation";
+    pub const PARTICLES: &str = "particles";
     pub const EGL: &str = "egl";
     pub const X11: &str = "x11";
     pub const WAYLAND: &str = "wayland";
@@ -127,6 +127,19 @@ impl FinalBuildConfiguration {
                 args.push(("skia_use_icu", no()));
             }
 
+            if features.svg {
+                args.push(("skia_enable_svg", yes()));
+            }
+
+            if features.animation {
+                args.push(("skia_enable_skottie", yes()));
+            }
+
+            // modules/svg and modules/skottie shape text with skshaper.
+            if features.skshaper() && !features.text_layout {
+                args.push(("skia_enable_skshaper", yes()));
+            }
+
             if features.webp_encode || features.webp_decode {
                 args.push(("skia_use_system_libwebp", yes_if(use_system_libraries)))
             }
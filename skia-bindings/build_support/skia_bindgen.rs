@@ -51,6 +51,21 @@ impl FinalBuildConfiguration {
             if features.text_layout {
                 sources.extend(vec!["src/shaper.cpp".into(), "src/paragraph.cpp".into()]);
             }
+            if features.skresources() {
+                sources.push("src/resources.cpp".into());
+            }
+            if features.svg {
+                sources.push("src/svg_dom.cpp".into());
+            }
+            if features.animation {
+                sources.push("src/skottie.cpp".into());
+            }
+            if features.particles {
+                sources.push("src/particles.cpp".into());
+            }
+            if features.webp_encode {
+                sources.push("src/webp_encode.cpp".into());
+            }
             sources.push("src/svg.cpp".into());
             sources
         };
@@ -85,6 +100,7 @@ pub fn generate_bindings(build: &FinalBuildConfiguration, output_directory: &Pat
         .constified_enum("SkCanvas_SaveLayerFlagsSet")
         .constified_enum("GrVkAlloc_Flag")
         .constified_enum("GrGLBackendState")
+        .constified_enum("SkPngEncoder_FilterFlag")
         // not used:
         .blocklist_type("SkPathRef_Editor")
         .blocklist_function("SkPathRef_Editor_Editor")
@@ -311,6 +327,7 @@ const OPAQUE_TYPES: &[&str] = &[
     // Ubuntu 18 LLVM 6: all types derived from SkWeakRefCnt
     "SkWeakRefCnt",
     "GrContext",
+    "SkAnimatedImage",
     "GrGLInterface",
     "GrSurfaceProxy",
     "Sk2DPathEffect",
@@ -359,6 +376,7 @@ const OPAQUE_TYPES: &[&str] = &[
     "SkFlattenable",
     "SkFontMgr",
     "SkFontStyleSet",
+    "SkImage_AsyncReadResult",
     "SkMaskFilter",
     "SkPathEffect",
     "SkPicture_AbortCallback",
@@ -409,6 +427,20 @@ const OPAQUE_TYPES: &[&str] = &[
     "GrD3DMemoryAllocator",
     // m87, yuva_pixmaps
     "std::tuple",
+    // modules/skresources, modules/svg
+    "skresources::.*",
+    "SkSVGDOM",
+    "SkSVGDOM_Builder",
+    "SkSVGNode",
+    "SkSVGSVG",
+    // modules/skottie
+    "skottie::.*",
+    "sksg::.*",
+    // modules/particles
+    "SkParticleEffect",
+    "SkParticleEffectParams",
+    "skjson::.*",
+    "SkSL::.*",
 ];
 
 const BLOCKLISTED_TYPES: &[&str] = &[
@@ -460,6 +492,14 @@ const ENUM_TABLE: &[EnumEntry] = &[
     ("DocumentStructureType", rewrite::k_xxx),
     ("ZeroInitialized", rewrite::k_xxx_name),
     ("SelectionPolicy", rewrite::k_xxx),
+    ("DisposalMethod", rewrite::k_xxx),
+    ("Blend", rewrite::k_xxx),
+    //
+    // encode/
+    //
+    ("Downsample", rewrite::name_xxx),
+    ("AlphaOption", rewrite::k_xxx),
+    ("Compression", rewrite::k_xxx),
     //
     // core/ effects/
     //
@@ -512,6 +552,8 @@ const ENUM_TABLE: &[EnumEntry] = &[
     ("InvertStyle", rewrite::k_xxx),
     // SkImage_*
     ("BitDepth", rewrite::k_xxx),
+    ("RescaleGamma", rewrite::k_xxx),
+    ("RescaleMode", rewrite::k_xxx),
     ("CachingHint", rewrite::k_xxx_name),
     ("CompressionType", rewrite::k_xxx),
     // SkImageFilter_MapDirection
@@ -537,6 +579,8 @@ const ENUM_TABLE: &[EnumEntry] = &[
     // SkSurface_*
     ("ContentChangeMode", rewrite::k_xxx_name),
     ("BackendHandleAccess", rewrite::k_xxx_name),
+    // SkTraceMemoryDump_LevelOfDetail
+    ("LevelOfDetail", rewrite::k_xxx_name),
     // SkTextUtils_Align
     ("Align", rewrite::k_xxx_name),
     // SkTrimPathEffect_Mode
@@ -636,6 +680,10 @@ pub(crate) mod rewrite {
         }
     }
 
+    pub fn name_xxx(name: &str, variant: &str) -> String {
+        format!("{}{}", name, k_xxx(name, variant))
+    }
+
     pub fn _k_xxx_enum(name: &str, variant: &str) -> String {
         capture(name, variant, &format!("k(.*)_{}", name))
     }
@@ -750,6 +798,21 @@ pub(crate) mod definitions {
                 "obj/modules/skparagraph/skparagraph.ninja".into(),
             ]);
         }
+        if features.skresources() {
+            files.push("obj/modules/skresources/skresources.ninja".into());
+        }
+        if features.svg {
+            files.push("obj/modules/svg/svg.ninja".into());
+        }
+        if features.animation {
+            files.extend(vec![
+                "obj/modules/sksg/sksg.ninja".into(),
+                "obj/modules/skottie/skottie.ninja".into(),
+            ]);
+        }
+        if features.particles {
+            files.push("obj/modules/particles/particles.ninja".into());
+        }
         files
     }
 
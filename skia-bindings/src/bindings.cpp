@@ -1,13 +1,24 @@
+#include <algorithm>
 #include <cassert>
+#include <cstdint>
+#include <cstring>
+#include <functional>
+#include <memory>
+#include <mutex>
+#include <new>
 #include <tuple>
 #include <vector>
 
 #include "bindings.h"
+// android/
+#include "include/android/SkAnimatedImage.h"
 // codec/
+#include "include/codec/SkAndroidCodec.h"
 #include "include/codec/SkEncodedOrigin.h"
 #include "include/codec/SkCodec.h"
 // core/
 #include "include/core/SkAnnotation.h"
+#include "include/core/SkBBHFactory.h"
 #include "include/core/SkBlendMode.h"
 #include "include/core/SkCanvas.h"
 #include "include/core/SkColor.h"
@@ -25,6 +36,7 @@
 #include "include/core/SkFontMetrics.h"
 #include "include/core/SkFontMgr.h"
 #include "include/core/SkGraphics.h"
+#include "include/core/SkICC.h"
 #include "include/core/SkImage.h"
 #include "include/core/SkImageEncoder.h"
 #include "include/core/SkImageFilter.h"
@@ -43,11 +55,13 @@
 #include "include/core/SkPixelRef.h"
 #include "include/core/SkPoint.h"
 #include "include/core/SkPoint3.h"
+#include "include/core/SkRasterHandleAllocator.h"
 #include "include/core/SkRect.h"
 #include "include/core/SkRefCnt.h"
 #include "include/core/SkRegion.h"
 #include "include/core/SkRRect.h"
 #include "include/core/SkRSXform.h"
+#include "include/core/SkSerialProcs.h"
 #include "include/core/SkStream.h"
 #include "include/core/SkStrokeRec.h"
 #include "include/core/SkSurface.h"
@@ -82,12 +96,21 @@
 #include "include/effects/SkTableColorFilter.h"
 #include "include/effects/SkTableMaskFilter.h"
 #include "include/effects/SkTrimPathEffect.h"
+// encode/
+#include "include/encode/SkJpegEncoder.h"
+#include "include/encode/SkPngEncoder.h"
 
 // pathops/
 #include "include/pathops/SkPathOps.h"
+// ports/
+#if defined(SK_BUILD_FOR_UNIX) || defined(SK_BUILD_FOR_ANDROID)
+#include "include/ports/SkFontMgr_directory.h"
+#include "include/ports/SkFontMgr_empty.h"
+#endif
 // utils/
 #include "include/utils/SkCamera.h"
 #include "include/utils/SkCustomTypeface.h"
+#include "include/utils/SkFrontBufferedStream.h"
 #include "include/utils/SkInterpolator.h"
 #include "include/utils/SkNullCanvas.h"
 #include "include/utils/SkParsePath.h"
@@ -102,6 +125,17 @@ extern "C" SkCodec* C_SkCodec_MakeFromData(SkData* data) {
     return SkCodec::MakeFromData(sp(data)).release();
 }
 
+extern "C" SkCodec* C_SkCodec_MakeFromStream(SkStream* stream) {
+    return SkCodec::MakeFromStream(std::unique_ptr<SkStream>(stream)).release();
+}
+
+// Buffers the beginning of a stream that can not be rewound, so that the codecs can peek at it
+// to detect the format.
+// note: this function _consumes_ / deletes the stream.
+extern "C" SkStream* C_SkCodec_NewBufferedStream(SkStream* stream) {
+    return SkFrontBufferedStream::Make(std::unique_ptr<SkStream>(stream), SkCodec::MinBufferedBytesNeeded()).release();
+}
+
 extern "C" void C_SkCodec_getInfo(const SkCodec* self, SkImageInfo* info) {
     *info = self->getInfo();
 }
@@ -114,6 +148,11 @@ extern "C" SkIRect C_SkCodec_bounds(const SkCodec* self) {
     return self->bounds();
 }
 
+extern "C" SkData* C_SkCodec_getICCProfile(const SkCodec* self) {
+    auto profile = self->getICCProfile();
+    return profile ? SkData::MakeWithCopy(profile->buffer, profile->size).release() : nullptr;
+}
+
 extern "C" SkEncodedOrigin C_SkCodec_getOrigin(const SkCodec* self) {
     return self->getOrigin();
 }
@@ -154,10 +193,133 @@ extern "C" int C_SkCodec_getFrameCount(SkCodec* self) {
     return self->getFrameCount();
 }
 
+extern "C" bool C_SkCodec_getFrameInfo(const SkCodec* self, int index, SkCodec::FrameInfo* info) {
+    return self->getFrameInfo(index, info);
+}
+
 extern "C" int C_SkCodec_getRepetitionCount(SkCodec* self) {
     return self->getRepetitionCount();
 }
 
+//
+// android/SkAnimatedImage.h
+//
+
+static SkAnimatedImage* makeAnimatedImage(std::unique_ptr<SkCodec> codec) {
+    auto androidCodec = SkAndroidCodec::MakeFromCodec(std::move(codec));
+    if (!androidCodec) {
+        return nullptr;
+    }
+    return SkAnimatedImage::Make(std::move(androidCodec)).release();
+}
+
+extern "C" SkAnimatedImage* C_SkAnimatedImage_MakeFromData(SkData* data) {
+    return makeAnimatedImage(SkCodec::MakeFromData(sp(data)));
+}
+
+extern "C" SkAnimatedImage* C_SkAnimatedImage_MakeFromStream(SkStream* stream) {
+    return makeAnimatedImage(SkCodec::MakeFromStream(std::unique_ptr<SkStream>(stream)));
+}
+
+extern "C" void C_SkAnimatedImage_reset(SkAnimatedImage* self) {
+    self->reset();
+}
+
+extern "C" bool C_SkAnimatedImage_isFinished(const SkAnimatedImage* self) {
+    return self->isFinished();
+}
+
+extern "C" int C_SkAnimatedImage_decodeNextFrame(SkAnimatedImage* self) {
+    return self->decodeNextFrame();
+}
+
+extern "C" SkImage* C_SkAnimatedImage_getCurrentFrame(SkAnimatedImage* self) {
+    return self->getCurrentFrame().release();
+}
+
+extern "C" int C_SkAnimatedImage_currentFrameDuration(SkAnimatedImage* self) {
+    return self->currentFrameDuration();
+}
+
+extern "C" void C_SkAnimatedImage_setRepetitionCount(SkAnimatedImage* self, int count) {
+    self->setRepetitionCount(count);
+}
+
+extern "C" int C_SkAnimatedImage_getRepetitionCount(const SkAnimatedImage* self) {
+    return self->getRepetitionCount();
+}
+
+extern "C" int C_SkAnimatedImage_getFrameCount(const SkAnimatedImage* self) {
+    return self->getFrameCount();
+}
+
+//
+// codec/SkAndroidCodec.h
+//
+
+extern "C" SkAndroidCodec* C_SkAndroidCodec_MakeFromData(SkData* data) {
+    return SkAndroidCodec::MakeFromData(sp(data)).release();
+}
+
+extern "C" SkAndroidCodec* C_SkAndroidCodec_MakeFromStream(SkStream* stream) {
+    return SkAndroidCodec::MakeFromStream(std::unique_ptr<SkStream>(stream)).release();
+}
+
+extern "C" void C_SkAndroidCodec_delete(SkAndroidCodec* self) {
+    delete self;
+}
+
+extern "C" void C_SkAndroidCodec_getInfo(const SkAndroidCodec* self, SkImageInfo* info) {
+    *info = self->getInfo();
+}
+
+extern "C" SkData* C_SkAndroidCodec_getICCProfile(const SkAndroidCodec* self) {
+    auto profile = self->getICCProfile();
+    return profile ? SkData::MakeWithCopy(profile->buffer, profile->size).release() : nullptr;
+}
+
+extern "C" SkEncodedImageFormat C_SkAndroidCodec_getEncodedFormat(const SkAndroidCodec* self) {
+    return self->getEncodedFormat();
+}
+
+extern "C" SkColorType C_SkAndroidCodec_computeOutputColorType(SkAndroidCodec* self, SkColorType requestedColorType) {
+    return self->computeOutputColorType(requestedColorType);
+}
+
+extern "C" SkAlphaType C_SkAndroidCodec_computeOutputAlphaType(SkAndroidCodec* self, bool requestedUnpremul) {
+    return self->computeOutputAlphaType(requestedUnpremul);
+}
+
+extern "C" SkColorSpace* C_SkAndroidCodec_computeOutputColorSpace(SkAndroidCodec* self, SkColorType outputColorType, SkColorSpace* prefColorSpace) {
+    return self->computeOutputColorSpace(outputColorType, sp(prefColorSpace)).release();
+}
+
+extern "C" int C_SkAndroidCodec_computeSampleSize(const SkAndroidCodec* self, SkISize* size) {
+    return self->computeSampleSize(size);
+}
+
+extern "C" SkISize C_SkAndroidCodec_getSampledDimensions(const SkAndroidCodec* self, int sampleSize) {
+    return self->getSampledDimensions(sampleSize);
+}
+
+extern "C" bool C_SkAndroidCodec_getSupportedSubset(const SkAndroidCodec* self, SkIRect* desiredSubset) {
+    return self->getSupportedSubset(desiredSubset);
+}
+
+extern "C" SkISize C_SkAndroidCodec_getSampledSubsetDimensions(const SkAndroidCodec* self, int sampleSize, const SkIRect* subset) {
+    return self->getSampledSubsetDimensions(sampleSize, *subset);
+}
+
+extern "C" SkCodec::Result C_SkAndroidCodec_getAndroidPixels(
+    SkAndroidCodec* self, const SkImageInfo* info, void* pixels, size_t rowBytes,
+    SkCodec::ZeroInitialized zeroInitialized, SkIRect* subset, int sampleSize) {
+    SkAndroidCodec::AndroidOptions options;
+    options.fZeroInitialized = zeroInitialized;
+    options.fSubset = subset;
+    options.fSampleSize = sampleSize;
+    return self->getAndroidPixels(*info, pixels, rowBytes, &options);
+}
+
 //
 // codec/SkEncodedOrigin.h
 //
@@ -180,6 +342,15 @@ extern "C" SkPoint C_SkCubicMap_computeFromT(const SkCubicMap* self, float t) {
     return self->computeFromT(t);
 }
 
+//
+// core/SkGraphics.h
+//
+
+extern "C" void C_SkGraphics_DumpMemoryStatistics(const RustTraceMemoryDump::Param* param) {
+    RustTraceMemoryDump dump(*param);
+    SkGraphics::DumpMemoryStatistics(&dump);
+}
+
 //
 // core/SkSurface.h
 //
@@ -188,6 +359,10 @@ extern "C" SkSurface* C_SkSurface_MakeRasterDirect(const SkImageInfo* imageInfo,
     return SkSurface::MakeRasterDirect(*imageInfo, pixels, rowBytes, surfaceProps).release();
 }
 
+extern "C" SkSurface* C_SkSurface_MakeRasterDirectReleaseProc(const SkImageInfo* imageInfo, void* pixels, size_t rowBytes, void (*releaseProc)(void* pixels, void* context), void* context, const SkSurfaceProps* surfaceProps) {
+    return SkSurface::MakeRasterDirectReleaseProc(*imageInfo, pixels, rowBytes, releaseProc, context, surfaceProps).release();
+}
+
 extern "C" SkSurface* C_SkSurface_MakeRaster(const SkImageInfo* imageInfo, size_t rowBytes, const SkSurfaceProps* surfaceProps) {
     return SkSurface::MakeRaster(*imageInfo, rowBytes, surfaceProps).release();
 }
@@ -240,6 +415,43 @@ extern "C" bool C_SkSurface_draw(SkSurface* self, const SkDeferredDisplayList* d
     return self->draw(sp(displayList), xOffset, yOffset);
 }
 
+// Asynchronous pixel reads of SkSurface and SkImage pass the result to a Rust callback, which takes
+// ownership of it.
+
+extern "C" typedef void (*RustReadPixelsCallback)(void* context, const SkImage::AsyncReadResult* result);
+
+struct RustReadPixelsContext {
+    RustReadPixelsCallback callback;
+    void* context;
+};
+
+static void readPixelsCallback(SkImage::ReadPixelsContext context, std::unique_ptr<const SkImage::AsyncReadResult> result) {
+    auto rustContext = static_cast<RustReadPixelsContext*>(context);
+    rustContext->callback(rustContext->context, result.release());
+    delete rustContext;
+}
+
+extern "C" void C_SkSurface_asyncRescaleAndReadPixels(
+        SkSurface* self,
+        const SkImageInfo* info, const SkIRect* srcRect,
+        SkImage::RescaleGamma rescaleGamma, SkImage::RescaleMode rescaleMode,
+        RustReadPixelsCallback callback, void* context) {
+    self->asyncRescaleAndReadPixels(
+            *info, *srcRect, rescaleGamma, rescaleMode,
+            readPixelsCallback, new RustReadPixelsContext { callback, context });
+}
+
+extern "C" void C_SkSurface_asyncRescaleAndReadPixelsYUV420(
+        SkSurface* self,
+        SkYUVColorSpace yuvColorSpace, SkColorSpace* dstColorSpace,
+        const SkIRect* srcRect, const SkISize* dstSize,
+        SkImage::RescaleGamma rescaleGamma, SkImage::RescaleMode rescaleMode,
+        RustReadPixelsCallback callback, void* context) {
+    self->asyncRescaleAndReadPixelsYUV420(
+            yuvColorSpace, sp(dstColorSpace), *srcRect, *dstSize, rescaleGamma, rescaleMode,
+            readPixelsCallback, new RustReadPixelsContext { callback, context });
+}
+
 //
 // core/SkSurfaceCharacterization.h
 //
@@ -346,6 +558,43 @@ extern "C" SkImage* C_SkImage_reinterpretColorSpace(const SkImage* self, SkColor
     return self->reinterpretColorSpace(sp(newColorSpace)).release();
 }
 
+extern "C" void C_SkImage_asyncRescaleAndReadPixels(
+        const SkImage* self,
+        const SkImageInfo* info, const SkIRect* srcRect,
+        SkImage::RescaleGamma rescaleGamma, SkImage::RescaleMode rescaleMode,
+        RustReadPixelsCallback callback, void* context) {
+    self->asyncRescaleAndReadPixels(
+            *info, *srcRect, rescaleGamma, rescaleMode,
+            readPixelsCallback, new RustReadPixelsContext { callback, context });
+}
+
+extern "C" void C_SkImage_asyncRescaleAndReadPixelsYUV420(
+        const SkImage* self,
+        SkYUVColorSpace yuvColorSpace, SkColorSpace* dstColorSpace,
+        const SkIRect* srcRect, const SkISize* dstSize,
+        SkImage::RescaleGamma rescaleGamma, SkImage::RescaleMode rescaleMode,
+        RustReadPixelsCallback callback, void* context) {
+    self->asyncRescaleAndReadPixelsYUV420(
+            yuvColorSpace, sp(dstColorSpace), *srcRect, *dstSize, rescaleGamma, rescaleMode,
+            readPixelsCallback, new RustReadPixelsContext { callback, context });
+}
+
+extern "C" void C_SkImage_AsyncReadResult_delete(const SkImage::AsyncReadResult* self) {
+    delete self;
+}
+
+extern "C" int C_SkImage_AsyncReadResult_count(const SkImage::AsyncReadResult* self) {
+    return self->count();
+}
+
+extern "C" const void* C_SkImage_AsyncReadResult_data(const SkImage::AsyncReadResult* self, int i) {
+    return self->data(i);
+}
+
+extern "C" size_t C_SkImage_AsyncReadResult_rowBytes(const SkImage::AsyncReadResult* self, int i) {
+    return self->rowBytes(i);
+}
+
 //
 // core/SkImageEncoder.h
 //
@@ -358,6 +607,147 @@ extern "C" SkData *C_SkEncodeBitmap(const SkBitmap *src, SkEncodedImageFormat fo
     return SkEncodeBitmap(*src, format, quality).release();
 }
 
+static SkPngEncoder::Options makePngEncoderOptions(int filterFlags, int zlibLevel, const char* const* comments, size_t commentsCount) {
+    SkPngEncoder::Options options;
+    options.fFilterFlags = static_cast<SkPngEncoder::FilterFlag>(filterFlags);
+    options.fZLibLevel = zlibLevel;
+    if (commentsCount) {
+        std::vector<size_t> sizes;
+        for (size_t i = 0; i < commentsCount; ++i) {
+            sizes.push_back(strlen(comments[i]) + 1);
+        }
+        options.fComments = SkDataTable::MakeCopyArrays(
+            reinterpret_cast<const void* const*>(comments), sizes.data(), commentsCount);
+    }
+    return options;
+}
+
+static SkJpegEncoder::Options makeJpegEncoderOptions(int quality, SkJpegEncoder::Downsample downsample, SkJpegEncoder::AlphaOption alphaOption) {
+    SkJpegEncoder::Options options;
+    options.fQuality = quality;
+    options.fDownsample = downsample;
+    options.fAlphaOption = alphaOption;
+    return options;
+}
+
+extern "C" bool C_SkPngEncoder_Encode(
+    SkWStream* dst, const SkPixmap* src,
+    int filterFlags, int zlibLevel,
+    const char* const* comments, size_t commentsCount) {
+    return SkPngEncoder::Encode(dst, *src, makePngEncoderOptions(filterFlags, zlibLevel, comments, commentsCount));
+}
+
+extern "C" bool C_SkJpegEncoder_Encode(
+    SkWStream* dst, const SkPixmap* src,
+    int quality, SkJpegEncoder::Downsample downsample, SkJpegEncoder::AlphaOption alphaOption) {
+    return SkJpegEncoder::Encode(dst, *src, makeJpegEncoderOptions(quality, downsample, alphaOption));
+}
+
+// An SkEncoder that encodes rows which are provided band by band.
+class RowEncoder {
+public:
+    // Only the current band of rows is held in memory. The encoder addresses the rows of the
+    // source pixmap from the top of the image, so the pixmap is pointed at the band buffer
+    // offset by the rows that were already encoded.
+    explicit RowEncoder(const SkImageInfo& info)
+    : _info(info), _bandRows(0), _rowsEncoded(0) {
+    }
+
+    bool reserveBand(int rows) {
+        if (rows <= _bandRows) {
+            return true;
+        }
+        _band.reset(new (std::nothrow) uint8_t[_info.minRowBytes() * rows]);
+        _bandRows = _band ? rows : 0;
+        pointSourceAtBand();
+        return _band != nullptr;
+    }
+
+    bool encodeRows(const SkPixmap* rows) {
+        if (rows->width() != _info.width() || rows->colorType() != _info.colorType()
+            || rows->alphaType() != _info.alphaType() || rows->height() > _info.height() - _rowsEncoded) {
+            return false;
+        }
+        if (!reserveBand(rows->height())) {
+            return false;
+        }
+        auto rowBytes = _info.minRowBytes();
+        for (int y = 0; y < rows->height(); ++y) {
+            memcpy(_band.get() + y * rowBytes, rows->addr(0, y), rowBytes);
+        }
+        pointSourceAtBand();
+        if (!_encoder->encodeRows(rows->height())) {
+            return false;
+        }
+        _rowsEncoded += rows->height();
+        return true;
+    }
+
+    SkImageInfo _info;
+    std::unique_ptr<uint8_t[]> _band;
+    int _bandRows;
+    SkPixmap _src;
+    std::unique_ptr<SkEncoder> _encoder;
+    int _rowsEncoded;
+
+private:
+    void pointSourceAtBand() {
+        auto rowBytes = _info.minRowBytes();
+        auto top = reinterpret_cast<uintptr_t>(_band.get()) - static_cast<uintptr_t>(_rowsEncoded) * rowBytes;
+        _src.reset(_info, reinterpret_cast<const void*>(top), rowBytes);
+    }
+};
+
+static RowEncoder* makeRowEncoder(const SkImageInfo* info, std::function<std::unique_ptr<SkEncoder>(const SkPixmap&)> make) {
+    if (SkImageInfo::ByteSizeOverflowed(info->computeMinByteSize())) {
+        return nullptr;
+    }
+    std::unique_ptr<RowEncoder> rowEncoder(new (std::nothrow) RowEncoder(*info));
+    // The encoders require the source pixmap to have pixels when they are created.
+    if (!rowEncoder || !rowEncoder->reserveBand(1)) {
+        return nullptr;
+    }
+    rowEncoder->_encoder = make(rowEncoder->_src);
+    if (!rowEncoder->_encoder) {
+        return nullptr;
+    }
+    return rowEncoder.release();
+}
+
+extern "C" RowEncoder* C_RowEncoder_MakePng(
+    SkWStream* dst, const SkImageInfo* info,
+    int filterFlags, int zlibLevel,
+    const char* const* comments, size_t commentsCount) {
+    auto options = makePngEncoderOptions(filterFlags, zlibLevel, comments, commentsCount);
+    return makeRowEncoder(info, [&](const SkPixmap& src) {
+        return SkPngEncoder::Make(dst, src, options);
+    });
+}
+
+extern "C" RowEncoder* C_RowEncoder_MakeJpeg(
+    SkWStream* dst, const SkImageInfo* info,
+    int quality, SkJpegEncoder::Downsample downsample, SkJpegEncoder::AlphaOption alphaOption) {
+    auto options = makeJpegEncoderOptions(quality, downsample, alphaOption);
+    return makeRowEncoder(info, [&](const SkPixmap& src) {
+        return SkJpegEncoder::Make(dst, src, options);
+    });
+}
+
+extern "C" void C_RowEncoder_delete(RowEncoder* self) {
+    delete self;
+}
+
+extern "C" bool C_RowEncoder_encodeRows(RowEncoder* self, const SkPixmap* rows) {
+    return self->encodeRows(rows);
+}
+
+// Encodes `src` tagged with `colorSpace`, which the PNG, JPEG and WebP encoders embed as an ICC profile.
+extern "C" SkData *C_SkEncodePixmapWithColorSpace(const SkPixmap *src, SkColorSpace* colorSpace, SkEncodedImageFormat format, int quality) {
+    SkPixmap pixmap(*src);
+    pixmap.setColorSpace(sp(colorSpace));
+    return SkEncodePixmap(pixmap, format, quality).release();
+}
+
 //
 // core/SkData.h
 //
@@ -701,6 +1091,57 @@ extern "C" void C_SkCanvas_discard(SkCanvas* self) {
     self->discard();
 }
 
+extern "C" void* C_SkCanvas_accessTopRasterHandle(const SkCanvas* self) {
+    return self->accessTopRasterHandle();
+}
+
+extern "C" void C_SkCanvas_drawAtlas(SkCanvas* self, const SkImage* atlas, const SkRSXform xform[], const SkRect tex[], const SkColor colors[], int count, SkBlendMode mode, const SkSamplingOptions* sampling, const SkRect* cullRect, const SkPaint* paint) {
+    self->drawAtlas(atlas, xform, tex, colors, count, mode, *sampling, cullRect, paint);
+}
+
+//
+// core/SkRasterHandleAllocator.h
+//
+
+extern "C" typedef void (*RustRasterHandleAllocatorDrop)(void* data);
+extern "C" typedef bool (*RustRasterHandleAllocatorAllocHandle)(void* data, const SkImageInfo* info, SkRasterHandleAllocator::Rec* rec);
+extern "C" typedef void (*RustRasterHandleAllocatorUpdateHandle)(void* data, void* handle, const SkMatrix* matrix, const SkIRect* clipBounds);
+
+class RustRasterHandleAllocator : public SkRasterHandleAllocator {
+public:
+    struct Param {
+        void* data;
+        RustRasterHandleAllocatorDrop drop;
+        RustRasterHandleAllocatorAllocHandle allocHandle;
+        RustRasterHandleAllocatorUpdateHandle updateHandle;
+    };
+
+    explicit RustRasterHandleAllocator(const Param& param)
+    : _param(param) {
+    }
+
+    ~RustRasterHandleAllocator() override {
+        _param.drop(_param.data);
+    }
+
+    bool allocHandle(const SkImageInfo& info, Rec* rec) override {
+        return _param.allocHandle(_param.data, &info, rec);
+    }
+
+    void updateHandle(Handle handle, const SkMatrix& matrix, const SkIRect& clipBounds) override {
+        _param.updateHandle(_param.data, handle, &matrix, &clipBounds);
+    }
+
+private:
+    Param _param;
+};
+
+// The canvas takes ownership of the allocator, if the canvas can not be created, the allocator
+// gets dropped.
+extern "C" SkCanvas* C_SkRasterHandleAllocator_MakeCanvas(const RustRasterHandleAllocator::Param* param, const SkImageInfo* info) {
+    return SkRasterHandleAllocator::MakeCanvas(std::make_unique<RustRasterHandleAllocator>(*param), *info).release();
+}
+
 //
 // core/SkAutoCanvasRestore.h
 //
@@ -821,6 +1262,47 @@ extern "C" SkColorSpace* C_SkColorSpace_Deserialize(const void* data, size_t len
     return SkColorSpace::Deserialize(data, length).release();
 }
 
+extern "C" SkColorSpace* C_SkColorSpace_MakeRGB(const skcms_TransferFunction* transferFn, const skcms_Matrix3x3* toXYZ) {
+    return SkColorSpace::MakeRGB(*transferFn, *toXYZ).release();
+}
+
+extern "C" SkColorSpace* C_SkColorSpace_MakeFromICC(const void* data, size_t length) {
+    skcms_ICCProfile profile;
+    if (!skcms_Parse(data, length, &profile)) {
+        return nullptr;
+    }
+    return SkColorSpace::Make(profile).release();
+}
+
+extern "C" SkData* C_SkColorSpace_writeICCProfile(const SkColorSpace* self) {
+    skcms_TransferFunction transferFn;
+    skcms_Matrix3x3 toXYZD50;
+    if (!self->isNumericalTransferFn(&transferFn) || !self->toXYZD50(&toXYZD50)) {
+        return nullptr;
+    }
+    return SkWriteICCProfile(transferFn, toXYZD50).release();
+}
+
+extern "C" bool C_SkColorSpace_isNumericalTransferFn(const SkColorSpace* self, skcms_TransferFunction* transferFn) {
+    return self->isNumericalTransferFn(transferFn);
+}
+
+extern "C" void C_SkColorSpace_transferFn(const SkColorSpace* self, skcms_TransferFunction* transferFn) {
+    self->transferFn(transferFn);
+}
+
+extern "C" void C_SkColorSpace_invTransferFn(const SkColorSpace* self, skcms_TransferFunction* transferFn) {
+    self->invTransferFn(transferFn);
+}
+
+extern "C" bool C_SkColorSpace_toXYZD50(const SkColorSpace* self, skcms_Matrix3x3* toXYZD50) {
+    return self->toXYZD50(toXYZD50);
+}
+
+extern "C" void C_SkColorSpace_gamutTransformTo(const SkColorSpace* self, const SkColorSpace* dst, skcms_Matrix3x3* srcToDst) {
+    self->gamutTransformTo(dst, srcToDst);
+}
+
 //
 // SkM44
 //
@@ -957,6 +1439,28 @@ extern "C" bool C_SkBitmap_tryAllocPixels(SkBitmap* self) {
     return self->tryAllocPixels();
 }
 
+extern "C" typedef bool (*RustBitmapAllocatorAllocPixelRef)(void* data, SkBitmap* bitmap);
+
+class RustBitmapAllocator : public SkBitmap::Allocator {
+public:
+    RustBitmapAllocator(void* data, RustBitmapAllocatorAllocPixelRef allocPixelRef)
+    : _data(data), _allocPixelRef(allocPixelRef) {
+    }
+
+    bool allocPixelRef(SkBitmap* bitmap) override {
+        return _allocPixelRef(_data, bitmap);
+    }
+
+private:
+    void* _data;
+    RustBitmapAllocatorAllocPixelRef _allocPixelRef;
+};
+
+extern "C" bool C_SkBitmap_tryAllocPixelsWithAllocator(SkBitmap* self, void* data, RustBitmapAllocatorAllocPixelRef allocPixelRef) {
+    auto allocator = sk_make_sp<RustBitmapAllocator>(data, allocPixelRef);
+    return self->tryAllocPixels(allocator.get());
+}
+
 extern "C" SkIPoint C_SkBitmap_pixelRefOrigin(const SkBitmap* self) {
     return self->pixelRefOrigin();
 }
@@ -993,6 +1497,76 @@ extern "C" SkImage* C_SkBitmap_asImage(const SkBitmap* self) {
     return self->asImage().release();
 }
 
+//
+// core/SkSerialProcs.h
+//
+
+// Procs return null to let Skia serialize or deserialize the object.
+extern "C" typedef SkData* (*RustSerialPictureProc)(void* ctx, SkPicture* picture);
+extern "C" typedef SkData* (*RustSerialImageProc)(void* ctx, SkImage* image);
+extern "C" typedef SkData* (*RustSerialTypefaceProc)(void* ctx, SkTypeface* typeface);
+extern "C" typedef SkPicture* (*RustDeserialPictureProc)(void* ctx, const void* data, size_t length);
+extern "C" typedef SkImage* (*RustDeserialImageProc)(void* ctx, const void* data, size_t length);
+extern "C" typedef SkTypeface* (*RustDeserialTypefaceProc)(void* ctx, const void* data, size_t length);
+
+// Serial procs that forward to Rust closures.
+struct RustSerialProcs {
+    void* ctx;
+    RustSerialPictureProc picture;
+    RustSerialImageProc image;
+    RustSerialTypefaceProc typeface;
+};
+
+// Deserial procs that forward to Rust closures.
+struct RustDeserialProcs {
+    void* ctx;
+    RustDeserialPictureProc picture;
+    RustDeserialImageProc image;
+    RustDeserialTypefaceProc typeface;
+};
+
+static SkSerialProcs toSkSerialProcs(const RustSerialProcs* procs) {
+    SkSerialProcs serialProcs;
+    auto ctx = const_cast<RustSerialProcs*>(procs);
+    serialProcs.fPictureProc = [](SkPicture* picture, void* ctx) {
+        auto procs = static_cast<RustSerialProcs*>(ctx);
+        return sp(procs->picture(procs->ctx, picture));
+    };
+    serialProcs.fPictureCtx = ctx;
+    serialProcs.fImageProc = [](SkImage* image, void* ctx) {
+        auto procs = static_cast<RustSerialProcs*>(ctx);
+        return sp(procs->image(procs->ctx, image));
+    };
+    serialProcs.fImageCtx = ctx;
+    serialProcs.fTypefaceProc = [](SkTypeface* typeface, void* ctx) {
+        auto procs = static_cast<RustSerialProcs*>(ctx);
+        return sp(procs->typeface(procs->ctx, typeface));
+    };
+    serialProcs.fTypefaceCtx = ctx;
+    return serialProcs;
+}
+
+static SkDeserialProcs toSkDeserialProcs(const RustDeserialProcs* procs) {
+    SkDeserialProcs deserialProcs;
+    auto ctx = const_cast<RustDeserialProcs*>(procs);
+    deserialProcs.fPictureProc = [](const void* data, size_t length, void* ctx) {
+        auto procs = static_cast<RustDeserialProcs*>(ctx);
+        return sp(procs->picture(procs->ctx, data, length));
+    };
+    deserialProcs.fPictureCtx = ctx;
+    deserialProcs.fImageProc = [](const void* data, size_t length, void* ctx) {
+        auto procs = static_cast<RustDeserialProcs*>(ctx);
+        return sp(procs->image(procs->ctx, data, length));
+    };
+    deserialProcs.fImageCtx = ctx;
+    deserialProcs.fTypefaceProc = [](const void* data, size_t length, void* ctx) {
+        auto procs = static_cast<RustDeserialProcs*>(ctx);
+        return sp(procs->typeface(procs->ctx, data, length));
+    };
+    deserialProcs.fTypefaceCtx = ctx;
+    return deserialProcs;
+}
+
 //
 // core/SkPicture.h
 //
@@ -1005,10 +1579,20 @@ extern "C" SkPicture* C_SkPicture_MakeFromData2(const void* data, size_t size) {
     return SkPicture::MakeFromData(data, size).release();
 }
 
+extern "C" SkPicture* C_SkPicture_MakeFromDataWithProcs(const void* data, size_t size, const RustDeserialProcs* procs) {
+    auto deserialProcs = toSkDeserialProcs(procs);
+    return SkPicture::MakeFromData(data, size, &deserialProcs).release();
+}
+
 extern "C" SkData* C_SkPicture_serialize(const SkPicture* self) {
     return self->serialize().release();
 }
 
+extern "C" SkData* C_SkPicture_serializeWithProcs(const SkPicture* self, const RustSerialProcs* procs) {
+    auto serialProcs = toSkSerialProcs(procs);
+    return self->serialize(&serialProcs).release();
+}
+
 extern "C" SkPicture* C_SkPicture_MakePlaceholder(const SkRect& cull) {
     return SkPicture::MakePlaceholder(cull).release();
 }
@@ -1213,6 +1797,11 @@ extern "C" SkTypeface* C_SkTypeface_MakeFromFile(const char path[], int index) {
 }
 */
 
+// note: this function _consumes_ / deletes the stream.
+extern "C" SkTypeface* C_SkTypeface_MakeFromStream(SkStreamAsset* stream, int index) {
+    return SkTypeface::MakeFromStream(std::unique_ptr<SkStreamAsset>(stream), index).release();
+}
+
 extern "C" SkTypeface* C_SkTypeface_MakeFromData(SkData* data, int index) {
     return SkTypeface::MakeFromData(sp(data), index).release();
 }
@@ -1267,6 +1856,30 @@ extern "C" SkData* C_SkFlattenable_serialize(const SkFlattenable* self) {
     return self->serialize().release();
 }
 
+extern "C" SkData* C_SkFlattenable_serializeWithProcs(const SkFlattenable* self, const RustSerialProcs* procs) {
+    auto serialProcs = toSkSerialProcs(procs);
+    return self->serialize(&serialProcs).release();
+}
+
+extern "C" SkFlattenable::Type C_SkFlattenable_getFlattenableType(const SkFlattenable* self) {
+    return self->getFlattenableType();
+}
+
+// Returns the name the factory of `self` is registered with, or null if it is not registered.
+extern "C" const char* C_SkFlattenable_getFactoryName(const SkFlattenable* self) {
+    return SkFlattenable::FactoryToName(self->getFactory());
+}
+
+extern "C" bool C_SkFlattenable_IsRegistered(const char* name) {
+    return SkFlattenable::NameToFactory(name) != nullptr;
+}
+
+// Registers the factory that is registered with `name` under `alias`, too. `alias` must outlive
+// the process.
+extern "C" SkFlattenable* C_SkFlattenable_Deserialize(SkFlattenable::Type type, const void* data, size_t length) {
+    return SkFlattenable::Deserialize(type, data, length).release();
+}
+
 //
 // core/SkFont.h
 //
@@ -1361,10 +1974,261 @@ extern "C" SkTypeface* C_SkFontMgr_makeFromStream(const SkFontMgr* self, SkStrea
     return self->makeFromStream(std::unique_ptr<SkStreamAsset>(stream), ttcIndex).release();
 }
 
+// note: this function _consumes_ / deletes the stream.
+extern "C" SkTypeface* C_SkFontMgr_makeFromStreamWithArguments(const SkFontMgr* self, SkStreamAsset* stream, const SkFontArguments* arguments) {
+    return self->makeFromStream(std::unique_ptr<SkStreamAsset>(stream), *arguments).release();
+}
+
 extern "C" SkFontMgr* C_SkFontMgr_RefDefault() {
     return SkFontMgr::RefDefault().release();
 }
 
+//
+// ports/SkFontMgr_custom
+//
+
+#if defined(SK_BUILD_FOR_UNIX) || defined(SK_BUILD_FOR_ANDROID)
+
+// Skia's custom font managers do not match typefaces by character, so this font manager
+// forwards to them and falls back to the first typeface that contains the character.
+class CharacterFallbackFontMgr : public SkFontMgr {
+public:
+    explicit CharacterFallbackFontMgr(sk_sp<SkFontMgr> fontMgr)
+    : _fontMgr(std::move(fontMgr)) {
+    }
+
+protected:
+    int onCountFamilies() const override {
+        return _fontMgr->countFamilies();
+    }
+
+    void onGetFamilyName(int index, SkString* familyName) const override {
+        _fontMgr->getFamilyName(index, familyName);
+    }
+
+    SkFontStyleSet* onCreateStyleSet(int index) const override {
+        return _fontMgr->createStyleSet(index);
+    }
+
+    SkFontStyleSet* onMatchFamily(const char familyName[]) const override {
+        return _fontMgr->matchFamily(familyName);
+    }
+
+    SkTypeface* onMatchFamilyStyle(const char familyName[], const SkFontStyle& style) const override {
+        return _fontMgr->matchFamilyStyle(familyName, style);
+    }
+
+    SkTypeface* onMatchFamilyStyleCharacter(const char familyName[], const SkFontStyle& style,
+                                            const char* bcp47[], int bcp47Count,
+                                            SkUnichar character) const override {
+        auto typeface = _fontMgr->matchFamilyStyleCharacter(familyName, style, bcp47, bcp47Count, character);
+        if (typeface) {
+            return typeface;
+        }
+
+        auto matchCharacter = [&](SkFontStyleSet* styleSet) -> SkTypeface* {
+            sk_sp<SkFontStyleSet> set(styleSet);
+            if (!set) {
+                return nullptr;
+            }
+            sk_sp<SkTypeface> candidate(set->matchStyle(style));
+            if (candidate && candidate->unicharToGlyph(character) != 0) {
+                return candidate.release();
+            }
+            return nullptr;
+        };
+
+        if (familyName) {
+            if (auto fallback = matchCharacter(_fontMgr->matchFamily(familyName))) {
+                return fallback;
+            }
+        }
+        for (int i = 0; i < _fontMgr->countFamilies(); ++i) {
+            if (auto fallback = matchCharacter(_fontMgr->createStyleSet(i))) {
+                return fallback;
+            }
+        }
+        return nullptr;
+    }
+
+    sk_sp<SkTypeface> onMakeFromData(sk_sp<SkData> data, int ttcIndex) const override {
+        return _fontMgr->makeFromData(std::move(data), ttcIndex);
+    }
+
+    sk_sp<SkTypeface> onMakeFromStreamIndex(std::unique_ptr<SkStreamAsset> stream, int ttcIndex) const override {
+        return _fontMgr->makeFromStream(std::move(stream), ttcIndex);
+    }
+
+    sk_sp<SkTypeface> onMakeFromStreamArgs(std::unique_ptr<SkStreamAsset> stream, const SkFontArguments& args) const override {
+        return _fontMgr->makeFromStream(std::move(stream), args);
+    }
+
+    sk_sp<SkTypeface> onMakeFromFile(const char path[], int ttcIndex) const override {
+        return _fontMgr->makeFromFile(path, ttcIndex);
+    }
+
+    sk_sp<SkTypeface> onLegacyMakeTypeface(const char familyName[], SkFontStyle style) const override {
+        return _fontMgr->legacyMakeTypeface(familyName, style);
+    }
+
+private:
+    sk_sp<SkFontMgr> _fontMgr;
+};
+
+extern "C" SkFontMgr* C_SkFontMgr_NewCustomDirectory(const char* dir) {
+    return new CharacterFallbackFontMgr(SkFontMgr_New_Custom_Directory(dir));
+}
+
+// A font manager that provides the typefaces in font data. Unlike SkFontMgr_New_Custom_Embedded(),
+// which requires the data to outlive the font manager and all its typefaces, the typefaces keep
+// a reference to their data.
+class FontDataFontMgr : public SkFontMgr {
+    class StyleSet : public SkFontStyleSet {
+    public:
+        explicit StyleSet(SkString familyName)
+        : _familyName(std::move(familyName)) {
+        }
+
+        const SkString& familyName() const {
+            return _familyName;
+        }
+
+        void append(sk_sp<SkTypeface> typeface) {
+            _typefaces.push_back(std::move(typeface));
+        }
+
+        int count() override {
+            return static_cast<int>(_typefaces.size());
+        }
+
+        void getStyle(int index, SkFontStyle* style, SkString* name) override {
+            if (style) {
+                *style = _typefaces[index]->fontStyle();
+            }
+            if (name) {
+                name->reset();
+            }
+        }
+
+        SkTypeface* createTypeface(int index) override {
+            return SkRef(_typefaces[index].get());
+        }
+
+        SkTypeface* matchStyle(const SkFontStyle& pattern) override {
+            return matchStyleCSS3(pattern);
+        }
+
+    private:
+        SkString _familyName;
+        std::vector<sk_sp<SkTypeface>> _typefaces;
+    };
+
+public:
+    explicit FontDataFontMgr(const std::vector<sk_sp<SkData>>& datas)
+    : _fontMgr(SkFontMgr_New_Custom_Empty()) {
+        for (const auto& data : datas) {
+            for (int ttcIndex = 0;; ++ttcIndex) {
+                auto typeface = _fontMgr->makeFromData(data, ttcIndex);
+                if (!typeface) {
+                    break;
+                }
+                SkString familyName;
+                typeface->getFamilyName(&familyName);
+                auto family = findFamily(familyName.c_str());
+                if (!family) {
+                    _families.push_back(sk_make_sp<StyleSet>(familyName));
+                    family = _families.back().get();
+                }
+                family->append(std::move(typeface));
+            }
+        }
+    }
+
+protected:
+    int onCountFamilies() const override {
+        return static_cast<int>(_families.size());
+    }
+
+    void onGetFamilyName(int index, SkString* familyName) const override {
+        *familyName = _families[index]->familyName();
+    }
+
+    SkFontStyleSet* onCreateStyleSet(int index) const override {
+        return SkRef(_families[index].get());
+    }
+
+    SkFontStyleSet* onMatchFamily(const char familyName[]) const override {
+        return SkSafeRef(findFamily(familyName));
+    }
+
+    SkTypeface* onMatchFamilyStyle(const char familyName[], const SkFontStyle& style) const override {
+        auto family = findFamily(familyName);
+        return family ? family->matchStyle(style) : nullptr;
+    }
+
+    SkTypeface* onMatchFamilyStyleCharacter(const char[], const SkFontStyle&,
+                                            const char*[], int, SkUnichar) const override {
+        return nullptr;
+    }
+
+    sk_sp<SkTypeface> onMakeFromData(sk_sp<SkData> data, int ttcIndex) const override {
+        return _fontMgr->makeFromData(std::move(data), ttcIndex);
+    }
+
+    sk_sp<SkTypeface> onMakeFromStreamIndex(std::unique_ptr<SkStreamAsset> stream, int ttcIndex) const override {
+        return _fontMgr->makeFromStream(std::move(stream), ttcIndex);
+    }
+
+    sk_sp<SkTypeface> onMakeFromStreamArgs(std::unique_ptr<SkStreamAsset> stream, const SkFontArguments& args) const override {
+        return _fontMgr->makeFromStream(std::move(stream), args);
+    }
+
+    sk_sp<SkTypeface> onMakeFromFile(const char path[], int ttcIndex) const override {
+        return _fontMgr->makeFromFile(path, ttcIndex);
+    }
+
+    sk_sp<SkTypeface> onLegacyMakeTypeface(const char familyName[], SkFontStyle style) const override {
+        auto family = findFamily(familyName);
+        if (!family && !_families.empty()) {
+            family = _families.front().get();
+        }
+        if (family) {
+            return sk_sp<SkTypeface>(family->matchStyle(style));
+        }
+        return _fontMgr->legacyMakeTypeface(familyName, style);
+    }
+
+private:
+    StyleSet* findFamily(const char familyName[]) const {
+        if (!familyName) {
+            return nullptr;
+        }
+        for (const auto& family : _families) {
+            if (family->familyName().equals(familyName)) {
+                return family.get();
+            }
+        }
+        return nullptr;
+    }
+
+    sk_sp<SkFontMgr> _fontMgr;
+    std::vector<sk_sp<StyleSet>> _families;
+};
+
+extern "C" SkFontMgr* C_SkFontMgr_NewFromFontData(const SkData* const* datas, size_t count) {
+    std::vector<sk_sp<SkData>> fontDatas;
+    for (size_t i = 0; i < count; ++i) {
+        fontDatas.push_back(sk_ref_sp(datas[i]));
+    }
+    return new CharacterFallbackFontMgr(sk_make_sp<FontDataFontMgr>(fontDatas));
+}
+
+extern "C" SkFontMgr* C_SkFontMgr_NewCustomEmpty() {
+    return new CharacterFallbackFontMgr(SkFontMgr_New_Custom_Empty());
+}
+
+#endif
+
 //
 // core/SkFontParameters.h
 //
@@ -1427,6 +2291,10 @@ extern "C" void C_SkPictureRecorder_destruct(SkPictureRecorder *self) {
     self->~SkPictureRecorder();
 }
 
+extern "C" SkCanvas* C_SkPictureRecorder_beginRecordingWithBBH(SkPictureRecorder* self, const SkRect* bounds, SkBBoxHierarchy* bbh) {
+    return self->beginRecording(*bounds, sp(bbh));
+}
+
 extern "C" SkPicture* C_SkPictureRecorder_finishRecordingAsPicture(SkPictureRecorder* self, const SkRect* cullRect) {
     if (cullRect){
         return self->finishRecordingAsPictureWithCull(*cullRect).release();
@@ -1467,6 +2335,28 @@ extern "C" void C_SkPixelRef_notifyAddedToCache(SkPixelRef* self) {
     self->notifyAddedToCache();
 }
 
+extern "C" typedef void (*RustPixelRefRelease)(void* pixels, void* context);
+
+// A pixel ref that calls `release` with the pixels and `context` when it gets destroyed.
+class RustPixelRef : public SkPixelRef {
+public:
+    RustPixelRef(int width, int height, void* addr, size_t rowBytes, RustPixelRefRelease release, void* context)
+    : SkPixelRef(width, height, addr, rowBytes), _release(release), _context(context) {
+    }
+
+    ~RustPixelRef() override {
+        _release(this->pixels(), _context);
+    }
+
+private:
+    RustPixelRefRelease _release;
+    void* _context;
+};
+
+extern "C" SkPixelRef* C_RustPixelRef_New(int width, int height, void* addr, size_t rowBytes, RustPixelRefRelease release, void* context) {
+    return new RustPixelRef(width, height, addr, rowBytes, release, context);
+}
+
 //
 // core/SkPoint.h
 //
@@ -1647,6 +2537,40 @@ extern "C" bool C_SkDeferredDisplayList_unique(const SkDeferredDisplayList* self
     return self->unique();
 }
 
+//
+// core/SkBBHFactory.h
+//
+
+extern "C" typedef void (*SkBBoxHierarchySearchResult)(void* results, int index);
+
+extern "C" void C_SkBBoxHierarchy_insert(SkBBoxHierarchy* self, const SkRect rects[], int count) {
+    self->insert(rects, count);
+}
+
+extern "C" void C_SkBBoxHierarchy_search(const SkBBoxHierarchy* self, const SkRect* query, void* results, SkBBoxHierarchySearchResult pushResult) {
+    std::vector<int> indices;
+    self->search(*query, &indices);
+    for (auto index : indices) {
+        pushResult(results, index);
+    }
+}
+
+extern "C" size_t C_SkBBoxHierarchy_bytesUsed(const SkBBoxHierarchy* self) {
+    return self->bytesUsed();
+}
+
+extern "C" SkBBHFactory* C_SkRTreeFactory_new() {
+    return new SkRTreeFactory();
+}
+
+extern "C" void C_SkBBHFactory_delete(SkBBHFactory* self) {
+    delete self;
+}
+
+extern "C" SkBBoxHierarchy* C_SkBBHFactory_makeBBH(const SkBBHFactory* self) {
+    return (*self)().release();
+}
+
 //
 // core/SkDrawable.h
 //
@@ -1655,10 +2579,73 @@ extern "C" SkDrawable* C_SkDrawable_Deserialize(const void* data, size_t length)
     return SkDrawable::Deserialize(data, length).release();
 }
 
+extern "C" SkDrawable* C_SkDrawable_DeserializeWithProcs(const void* data, size_t length, const RustDeserialProcs* procs) {
+    auto deserialProcs = toSkDeserialProcs(procs);
+    return SkDrawable::Deserialize(data, length, &deserialProcs).release();
+}
+
 extern "C" SkRect C_SkDrawable_getBounds(SkDrawable* self) {
     return self->getBounds();
 }
 
+extern "C" SkPicture* C_SkDrawable_newPictureSnapshot(SkDrawable* self) {
+    return self->newPictureSnapshot();
+}
+
+extern "C" size_t C_SkDrawable_approximateBytesUsed(SkDrawable* self) {
+    return self->approximateBytesUsed();
+}
+
+//
+// Rust implementations of SkDrawable
+//
+
+extern "C" typedef void (*RustDrawableDrop)(void* data);
+extern "C" typedef void (*RustDrawableDraw)(void* data, SkCanvas* canvas);
+extern "C" typedef void (*RustDrawableGetBounds)(void* data, SkRect* bounds);
+extern "C" typedef size_t (*RustDrawableApproximateBytesUsed)(void* data);
+
+class RustDrawable : public SkDrawable {
+public:
+    struct Param {
+        void* data;
+        RustDrawableDrop drop;
+        RustDrawableDraw draw;
+        RustDrawableGetBounds getBounds;
+        RustDrawableApproximateBytesUsed approximateBytesUsed;
+    };
+
+    explicit RustDrawable(const Param& param)
+    : _param(param) {
+    }
+
+    ~RustDrawable() override {
+        _param.drop(_param.data);
+    }
+
+protected:
+    void onDraw(SkCanvas* canvas) override {
+        _param.draw(_param.data, canvas);
+    }
+
+    SkRect onGetBounds() override {
+        SkRect bounds = SkRect::MakeEmpty();
+        _param.getBounds(_param.data, &bounds);
+        return bounds;
+    }
+
+    size_t onApproximateBytesUsed() override {
+        return _param.approximateBytesUsed(_param.data);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkDrawable* C_RustDrawable_New(const RustDrawable::Param* param) {
+    return new RustDrawable(*param);
+}
+
 //
 // SkImageFilter
 //
@@ -1675,6 +2662,11 @@ extern "C" SkImageFilter* C_SkImageFilter_Deserialize(const void* data, size_t l
     return SkImageFilter::Deserialize(data, length).release();
 }
 
+extern "C" SkImageFilter* C_SkImageFilter_DeserializeWithProcs(const void* data, size_t length, const RustDeserialProcs* procs) {
+    auto deserialProcs = toSkDeserialProcs(procs);
+    return SkImageFilter::Deserialize(data, length, &deserialProcs).release();
+}
+
 extern "C" SkIRect C_SkImageFilter_filterBounds(
     const SkImageFilter* self, 
     const SkIRect* src, 
@@ -1728,6 +2720,48 @@ extern "C" SkImageGenerator *C_SkImageGenerator_MakeFromPicture(
             sp(cs)).release();
 }
 
+//
+// Rust implementations of SkImageGenerator
+//
+
+extern "C" typedef void (*RustImageGeneratorDrop)(void* data);
+extern "C" typedef bool (*RustImageGeneratorGetPixels)(void* data, const SkImageInfo* info, void* pixels, size_t rowBytes);
+extern "C" typedef SkData* (*RustImageGeneratorRefEncodedData)(void* data);
+
+class RustImageGenerator : public SkImageGenerator {
+public:
+    struct Param {
+        void* data;
+        RustImageGeneratorDrop drop;
+        RustImageGeneratorGetPixels getPixels;
+        RustImageGeneratorRefEncodedData refEncodedData;
+    };
+
+    RustImageGenerator(const SkImageInfo& info, const Param& param)
+    : SkImageGenerator(info), _param(param) {
+    }
+
+    ~RustImageGenerator() override {
+        _param.drop(_param.data);
+    }
+
+protected:
+    sk_sp<SkData> onRefEncodedData() override {
+        return sk_sp<SkData>(_param.refEncodedData(_param.data));
+    }
+
+    bool onGetPixels(const SkImageInfo& info, void* pixels, size_t rowBytes, const Options&) override {
+        return _param.getPixels(_param.data, &info, pixels, rowBytes);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkImageGenerator* C_RustImageGenerator_New(const SkImageInfo* info, const RustImageGenerator::Param* param) {
+    return new RustImageGenerator(*info, *param);
+}
+
 //
 // core/SkString.h
 //
@@ -1919,6 +2953,26 @@ extern "C" size_t C_SkStream_read(SkStream* stream, void* buffer, size_t len) {
     return stream->read(buffer, len);
 }
 
+extern "C" bool C_SkStream_rewind(SkStream* stream) {
+    return stream->rewind();
+}
+
+extern "C" bool C_SkStream_seek(SkStream* stream, size_t position) {
+    return stream->seek(position);
+}
+
+extern "C" size_t C_SkStream_getPosition(const SkStream* stream) {
+    return stream->getPosition();
+}
+
+extern "C" bool C_SkStream_hasLength(const SkStream* stream) {
+    return stream->hasLength();
+}
+
+extern "C" size_t C_SkStream_getLength(const SkStream* stream) {
+    return stream->getLength();
+}
+
 extern "C" size_t C_SkStreamAsset_getLength(const SkStreamAsset* self) {
     return self->getLength();
 }
@@ -1947,6 +3001,254 @@ extern "C" SkStreamAsset* C_SkDynamicMemoryWStream_detachAsStream(SkDynamicMemor
     return self->detachAsStream().release();
 }
 
+//
+// Rust streams
+//
+
+// Reads up to `count` bytes into `buffer`. If `buffer` is null, the bytes are skipped.
+// Returns the number of bytes read, which is less than `count` only at the end of the stream
+// or if an error occurred.
+extern "C" typedef size_t (*RustStreamRead)(void* data, void* buffer, size_t count);
+// Seeks to `offset` relative to the start (`whence` = 0), the current position (1), or the end (2)
+// of the stream. Returns the new position, or -1 if seeking failed.
+extern "C" typedef int64_t (*RustStreamSeek)(void* data, int64_t offset, int whence);
+extern "C" typedef void (*RustStreamDrop)(void* data);
+
+// A stream that forwards to a Rust reader. If `seek` is null, the stream can not be rewound and
+// has no position and length. If `drop` is not null, the stream owns the reader and drops it
+// when it gets destructed.
+class RustStream : public SkStream {
+public:
+    RustStream(void* data, RustStreamRead read, RustStreamSeek seek, RustStreamDrop drop)
+    : _data(data), _read(read), _seek(seek), _drop(drop), _isAtEnd(false) {
+    }
+
+    ~RustStream() override {
+        if (_drop) {
+            _drop(_data);
+        }
+    }
+
+    size_t read(void* buffer, size_t size) override {
+        auto bytesRead = _read(_data, buffer, size);
+        if (bytesRead < size) {
+            _isAtEnd = true;
+        }
+        return bytesRead;
+    }
+
+    bool isAtEnd() const override {
+        return _isAtEnd;
+    }
+
+    bool rewind() override {
+        return seek(0);
+    }
+
+    bool hasPosition() const override {
+        return _seek != nullptr;
+    }
+
+    size_t getPosition() const override {
+        return _seek ? std::max<int64_t>(_seek(_data, 0, 1), 0) : 0;
+    }
+
+    bool seek(size_t position) override {
+        return seekTo(position, 0);
+    }
+
+    bool move(long offset) override {
+        return seekTo(offset, 1);
+    }
+
+    bool hasLength() const override {
+        return _seek != nullptr;
+    }
+
+    size_t getLength() const override {
+        if (!_seek) {
+            return 0;
+        }
+        auto position = _seek(_data, 0, 1);
+        auto length = _seek(_data, 0, 2);
+        _seek(_data, position, 0);
+        return std::max<int64_t>(length, 0);
+    }
+
+private:
+    bool seekTo(int64_t offset, int whence) {
+        if (!_seek || _seek(_data, offset, whence) < 0) {
+            return false;
+        }
+        _isAtEnd = false;
+        return true;
+    }
+
+    void* _data;
+    RustStreamRead _read;
+    RustStreamSeek _seek;
+    RustStreamDrop _drop;
+    bool _isAtEnd;
+};
+
+extern "C" void C_RustStream_construct(RustStream* uninitialized, void* data, RustStreamRead read) {
+    new(uninitialized) RustStream(data, read, nullptr, nullptr);
+}
+
+extern "C" void C_RustStream_destruct(RustStream* self) {
+    self->~RustStream();
+}
+
+extern "C" RustStream* C_RustStream_New(void* data, RustStreamRead read, RustStreamSeek seek, RustStreamDrop drop) {
+    return new RustStream(data, read, seek, drop);
+}
+
+// A stream asset that forwards to a seekable Rust reader. Duplicated and forked streams share the
+// reader, but have their own position, so the reader is seeked before every read. The reader is
+// dropped when the last stream that shares it gets destructed.
+class RustStreamAsset : public SkStreamAsset {
+    struct Reader {
+        Reader(void* data, RustStreamRead read, RustStreamSeek seek, RustStreamDrop drop)
+        : data(data), read(read), seek(seek), drop(drop), length(std::max<int64_t>(seek(data, 0, 2), 0)) {
+        }
+
+        ~Reader() {
+            drop(data);
+        }
+
+        void* data;
+        RustStreamRead read;
+        RustStreamSeek seek;
+        RustStreamDrop drop;
+        size_t length;
+        std::mutex mutex;
+    };
+
+public:
+    RustStreamAsset(void* data, RustStreamRead read, RustStreamSeek seek, RustStreamDrop drop)
+    : _reader(std::make_shared<Reader>(data, read, seek, drop)), _position(0) {
+    }
+
+    size_t read(void* buffer, size_t size) override {
+        size = std::min(size, _reader->length - _position);
+        if (size == 0) {
+            return 0;
+        }
+        std::lock_guard<std::mutex> lock(_reader->mutex);
+        if (_reader->seek(_reader->data, static_cast<int64_t>(_position), 0) < 0) {
+            return 0;
+        }
+        auto bytesRead = _reader->read(_reader->data, buffer, size);
+        _position += bytesRead;
+        return bytesRead;
+    }
+
+    bool isAtEnd() const override {
+        return _position >= _reader->length;
+    }
+
+    bool rewind() override {
+        _position = 0;
+        return true;
+    }
+
+    bool hasPosition() const override {
+        return true;
+    }
+
+    size_t getPosition() const override {
+        return _position;
+    }
+
+    bool seek(size_t position) override {
+        _position = std::min(position, _reader->length);
+        return true;
+    }
+
+    bool move(long offset) override {
+        if (offset < 0 && static_cast<size_t>(-offset) > _position) {
+            _position = 0;
+        } else {
+            _position = std::min(_position + offset, _reader->length);
+        }
+        return true;
+    }
+
+    bool hasLength() const override {
+        return true;
+    }
+
+    size_t getLength() const override {
+        return _reader->length;
+    }
+
+private:
+    RustStreamAsset(std::shared_ptr<Reader> reader, size_t position)
+    : _reader(std::move(reader)), _position(position) {
+    }
+
+    SkStreamAsset* onDuplicate() const override {
+        return new RustStreamAsset(_reader, 0);
+    }
+
+    SkStreamAsset* onFork() const override {
+        return new RustStreamAsset(_reader, _position);
+    }
+
+    std::shared_ptr<Reader> _reader;
+    size_t _position;
+};
+
+extern "C" SkStreamAsset* C_RustStreamAsset_New(void* data, RustStreamRead read, RustStreamSeek seek, RustStreamDrop drop) {
+    return new RustStreamAsset(data, read, seek, drop);
+}
+
+extern "C" SkStreamAsset* C_SkStreamAsset_duplicate(const SkStreamAsset* self) {
+    return self->duplicate().release();
+}
+
+// Writes all `count` bytes of `buffer`, returns false if an error occurred.
+extern "C" typedef bool (*RustWStreamWrite)(void* data, const void* buffer, size_t count);
+extern "C" typedef void (*RustWStreamFlush)(void* data);
+
+// A write stream that forwards to a Rust writer.
+class RustWStream : public SkWStream {
+public:
+    RustWStream(void* data, RustWStreamWrite write, RustWStreamFlush flush)
+    : _data(data), _write(write), _flush(flush), _bytesWritten(0) {
+    }
+
+    bool write(const void* buffer, size_t size) override {
+        if (!_write(_data, buffer, size)) {
+            return false;
+        }
+        _bytesWritten += size;
+        return true;
+    }
+
+    void flush() override {
+        _flush(_data);
+    }
+
+    size_t bytesWritten() const override {
+        return _bytesWritten;
+    }
+
+private:
+    void* _data;
+    RustWStreamWrite _write;
+    RustWStreamFlush _flush;
+    size_t _bytesWritten;
+};
+
+extern "C" void C_RustWStream_construct(RustWStream* uninitialized, void* data, RustWStreamWrite write, RustWStreamFlush flush) {
+    new(uninitialized) RustWStream(data, write, flush);
+}
+
+extern "C" void C_RustWStream_destruct(RustWStream* self) {
+    self->~RustWStream();
+}
+
 //
 // core/SkYUVAInfo.h
 //
@@ -3,6 +3,7 @@
 
 #include "include/core/SkRefCnt.h"
 #include "include/core/SkString.h"
+#include "include/core/SkTraceMemoryDump.h"
 #include <vector>
 
 template<typename T>
@@ -21,4 +22,58 @@ struct SkStrings {
     std::vector<SkString> strings;
 };
 
+// A SkTraceMemoryDump that forwards to a Rust implementation, used by SkGraphics and
+// GrDirectContext.
+
+extern "C" typedef void (*RustTraceMemoryDumpNumericValue)(void* data, const char* dumpName, const char* valueName, const char* units, uint64_t value);
+extern "C" typedef void (*RustTraceMemoryDumpStringValue)(void* data, const char* dumpName, const char* valueName, const char* value);
+extern "C" typedef void (*RustTraceMemoryDumpSetMemoryBacking)(void* data, const char* dumpName, const char* backingType, const char* backingObjectId);
+extern "C" typedef SkTraceMemoryDump::LevelOfDetail (*RustTraceMemoryDumpGetRequestedDetails)(void* data);
+extern "C" typedef bool (*RustTraceMemoryDumpShouldDumpWrappedObjects)(void* data);
+
+class RustTraceMemoryDump : public SkTraceMemoryDump {
+public:
+    struct Param {
+        void* data;
+        RustTraceMemoryDumpNumericValue dumpNumericValue;
+        RustTraceMemoryDumpStringValue dumpStringValue;
+        RustTraceMemoryDumpSetMemoryBacking setMemoryBacking;
+        RustTraceMemoryDumpGetRequestedDetails getRequestedDetails;
+        RustTraceMemoryDumpShouldDumpWrappedObjects shouldDumpWrappedObjects;
+    };
+
+    explicit RustTraceMemoryDump(const Param& param)
+    : _param(param) {
+    }
+
+    ~RustTraceMemoryDump() override = default;
+
+    void dumpNumericValue(const char* dumpName, const char* valueName, const char* units, uint64_t value) override {
+        _param.dumpNumericValue(_param.data, dumpName, valueName, units, value);
+    }
+
+    void dumpStringValue(const char* dumpName, const char* valueName, const char* value) override {
+        _param.dumpStringValue(_param.data, dumpName, valueName, value);
+    }
+
+    void setMemoryBacking(const char* dumpName, const char* backingType, const char* backingObjectId) override {
+        _param.setMemoryBacking(_param.data, dumpName, backingType, backingObjectId);
+    }
+
+    // Discardable memory can not be represented in Rust.
+    void setDiscardableMemoryBacking(const char*, const SkDiscardableMemory&) override {
+    }
+
+    LevelOfDetail getRequestedDetails() const override {
+        return _param.getRequestedDetails(_param.data);
+    }
+
+    bool shouldDumpWrappedObjects() const override {
+        return _param.shouldDumpWrappedObjects(_param.data);
+    }
+
+private:
+    Param _param;
+};
+
 #endif //SKIA_BINDINGS_BINDINGS_H
@@ -233,6 +233,11 @@ extern "C" void C_GrDirectContext_performDeferredCleanup(GrDirectContext* self,
     self->performDeferredCleanup(std::chrono::milliseconds(msNotUsed));
 }
 
+extern "C" void C_GrDirectContext_dumpMemoryStatistics(const GrDirectContext* self, const RustTraceMemoryDump::Param* param) {
+    RustTraceMemoryDump dump(*param);
+    self->dumpMemoryStatistics(&dump);
+}
+
 //
 // gpu/GrContextOptions.h
 //
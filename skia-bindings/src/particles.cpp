@@ -0,0 +1,82 @@
+#include <cstring>
+#include <mutex>
+
+#include "bindings.h"
+#include "include/core/SkCanvas.h"
+#include "modules/particles/include/SkParticleEffect.h"
+#include "modules/particles/include/SkParticleSerialization.h"
+#include "modules/skresources/include/SkResources.h"
+#include "src/sksl/codegen/SkSLVMCodeGenerator.h"
+#include "src/utils/SkJSON.h"
+
+//
+// modules/particles/include/SkParticleEffect.h
+//
+
+extern "C" SkParticleEffectParams* C_SkParticleEffectParams_MakeFromJSON(const char* json, size_t length, const skresources::ResourceProvider* resourceProvider) {
+    static std::once_flag registerParticleTypes;
+    std::call_once(registerParticleTypes, SkParticleEffect::RegisterParticleTypes);
+
+    skjson::DOM dom(json, length);
+    if (!dom.root().is<skjson::ObjectValue>()) {
+        return nullptr;
+    }
+    SkFromJsonVisitor fromJson(dom.root());
+    auto params = sk_make_sp<SkParticleEffectParams>();
+    params->visitFields(&fromJson);
+    params->prepare(resourceProvider);
+    return params.release();
+}
+
+extern "C" SkParticleEffect* C_SkParticleEffect_Make(SkParticleEffectParams* params) {
+    return new SkParticleEffect(sp(params));
+}
+
+extern "C" void C_SkParticleEffect_start(SkParticleEffect* self, double now, bool looping, const SkPoint* position) {
+    self->start(now, looping, *position);
+}
+
+extern "C" void C_SkParticleEffect_update(SkParticleEffect* self, double now) {
+    self->update(now);
+}
+
+extern "C" void C_SkParticleEffect_draw(SkParticleEffect* self, SkCanvas* canvas) {
+    self->draw(canvas);
+}
+
+extern "C" bool C_SkParticleEffect_isAlive(const SkParticleEffect* self) {
+    return self->isAlive();
+}
+
+extern "C" int C_SkParticleEffect_getCount(const SkParticleEffect* self) {
+    return self->getCount();
+}
+
+extern "C" void C_SkParticleEffect_setPosition(SkParticleEffect* self, const SkPoint* position) {
+    self->setPosition(*position);
+}
+
+extern "C" size_t C_SkParticleEffect_uniformCount(const SkParticleEffect* self) {
+    auto info = self->uniformInfo();
+    return info ? info->fUniforms.size() : 0;
+}
+
+extern "C" const char* C_SkParticleEffect_uniformName(const SkParticleEffect* self, size_t index) {
+    return self->uniformInfo()->fUniforms[index].fName.c_str();
+}
+
+// Returns a pointer to the values of the uniform `name` and sets `count` to the number of values,
+// or returns null if there is no uniform with that name.
+extern "C" float* C_SkParticleEffect_uniform(SkParticleEffect* self, const char* name, size_t* count) {
+    auto info = self->uniformInfo();
+    if (!info) {
+        return nullptr;
+    }
+    for (const auto& uniform : info->fUniforms) {
+        if (std::strcmp(uniform.fName.c_str(), name) == 0) {
+            *count = uniform.fColumns * uniform.fRows;
+            return self->uniformData() + uniform.fSlot;
+        }
+    }
+    return nullptr;
+}
@@ -0,0 +1,52 @@
+#include "bindings.h"
+#include "include/core/SkData.h"
+#include "modules/skresources/include/SkResources.h"
+
+//
+// Rust implementations of skresources::ResourceProvider
+//
+
+extern "C" typedef void (*RustResourceProviderDrop)(void* data);
+extern "C" typedef SkData* (*RustResourceProviderLoad)(void* data, const char* resourcePath, const char* resourceName);
+extern "C" typedef SkData* (*RustResourceProviderLoadFont)(void* data, const char* name, const char* url);
+
+class RustResourceProvider : public skresources::ResourceProvider {
+public:
+    struct Param {
+        void* data;
+        RustResourceProviderDrop drop;
+        RustResourceProviderLoad load;
+        RustResourceProviderLoadFont loadFont;
+    };
+
+    explicit RustResourceProvider(const Param& param)
+    : _param(param) {
+    }
+
+    ~RustResourceProvider() override {
+        _param.drop(_param.data);
+    }
+
+    sk_sp<SkData> load(const char resourcePath[], const char resourceName[]) const override {
+        return sp(_param.load(_param.data, resourcePath, resourceName));
+    }
+
+    sk_sp<skresources::ImageAsset> loadImageAsset(const char resourcePath[], const char resourceName[], const char[]) const override {
+        auto data = load(resourcePath, resourceName);
+        if (!data) {
+            return nullptr;
+        }
+        return skresources::MultiFrameImageAsset::Make(std::move(data));
+    }
+
+    sk_sp<SkData> loadFont(const char name[], const char url[]) const override {
+        return sp(_param.loadFont(_param.data, name, url));
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" skresources::ResourceProvider* C_RustResourceProvider_New(const RustResourceProvider::Param* param) {
+    return new RustResourceProvider(*param);
+}
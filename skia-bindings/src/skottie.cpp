@@ -0,0 +1,142 @@
+#include "bindings.h"
+#include "include/core/SkCanvas.h"
+#include "include/core/SkFontMgr.h"
+#include "include/core/SkStream.h"
+#include "modules/skottie/include/Skottie.h"
+#include "modules/skottie/include/SkottieProperty.h"
+#include "modules/skresources/include/SkResources.h"
+
+//
+// modules/skottie/include/Skottie.h
+//
+
+extern "C" skottie::Animation* C_skottie_Animation_MakeFromStream(
+    SkStream* stream,
+    SkFontMgr* fontMgr,
+    skresources::ResourceProvider* resourceProvider,
+    skottie::PropertyObserver* propertyObserver) {
+    skottie::Animation::Builder builder;
+    if (fontMgr) {
+        builder.setFontManager(sp(fontMgr));
+    }
+    if (resourceProvider) {
+        builder.setResourceProvider(sp(resourceProvider));
+    }
+    if (propertyObserver) {
+        builder.setPropertyObserver(sp(propertyObserver));
+    }
+    return builder.make(stream).release();
+}
+
+extern "C" void C_skottie_Animation_ref(const skottie::Animation* self) {
+    self->ref();
+}
+
+extern "C" void C_skottie_Animation_unref(const skottie::Animation* self) {
+    self->unref();
+}
+
+extern "C" bool C_skottie_Animation_unique(const skottie::Animation* self) {
+    return self->unique();
+}
+
+extern "C" void C_skottie_Animation_render(const skottie::Animation* self, SkCanvas* canvas, const SkRect* dst, uint32_t flags) {
+    self->render(canvas, dst, flags);
+}
+
+extern "C" void C_skottie_Animation_seekFrame(skottie::Animation* self, double t) {
+    self->seekFrame(t);
+}
+
+extern "C" void C_skottie_Animation_seekFrameTime(skottie::Animation* self, double t) {
+    self->seekFrameTime(t);
+}
+
+extern "C" double C_skottie_Animation_duration(const skottie::Animation* self) {
+    return self->duration();
+}
+
+extern "C" double C_skottie_Animation_fps(const skottie::Animation* self) {
+    return self->fps();
+}
+
+extern "C" double C_skottie_Animation_inPoint(const skottie::Animation* self) {
+    return self->inPoint();
+}
+
+extern "C" double C_skottie_Animation_outPoint(const skottie::Animation* self) {
+    return self->outPoint();
+}
+
+extern "C" void C_skottie_Animation_size(const skottie::Animation* self, SkSize* size) {
+    *size = self->size();
+}
+
+extern "C" void C_skottie_Animation_version(const skottie::Animation* self, SkString* version) {
+    *version = self->version();
+}
+
+//
+// modules/skottie/include/SkottieProperty.h
+//
+
+extern "C" void C_skottie_ColorPropertyHandle_delete(skottie::ColorPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" SkColor C_skottie_ColorPropertyHandle_get(const skottie::ColorPropertyHandle* self) {
+    return self->get();
+}
+
+extern "C" void C_skottie_ColorPropertyHandle_set(skottie::ColorPropertyHandle* self, SkColor color) {
+    self->set(color);
+}
+
+extern "C" void C_skottie_OpacityPropertyHandle_delete(skottie::OpacityPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" float C_skottie_OpacityPropertyHandle_get(const skottie::OpacityPropertyHandle* self) {
+    return self->get();
+}
+
+extern "C" void C_skottie_OpacityPropertyHandle_set(skottie::OpacityPropertyHandle* self, float opacity) {
+    self->set(opacity);
+}
+
+extern "C" typedef void (*RustPropertyObserverDrop)(void* data);
+extern "C" typedef void (*RustPropertyObserverOnColorProperty)(void* data, const char* nodeName, skottie::ColorPropertyHandle* handle);
+extern "C" typedef void (*RustPropertyObserverOnOpacityProperty)(void* data, const char* nodeName, skottie::OpacityPropertyHandle* handle);
+
+class RustPropertyObserver : public skottie::PropertyObserver {
+public:
+    struct Param {
+        void* data;
+        RustPropertyObserverDrop drop;
+        RustPropertyObserverOnColorProperty onColorProperty;
+        RustPropertyObserverOnOpacityProperty onOpacityProperty;
+    };
+
+    explicit RustPropertyObserver(const Param& param)
+    : _param(param) {
+    }
+
+    ~RustPropertyObserver() override {
+        _param.drop(_param.data);
+    }
+
+    void onColorProperty(const char nodeName[], const LazyHandle<skottie::ColorPropertyHandle>& lh) override {
+        _param.onColorProperty(_param.data, nodeName, lh().release());
+    }
+
+    void onOpacityProperty(const char nodeName[], const LazyHandle<skottie::OpacityPropertyHandle>& lh) override {
+        _param.onOpacityProperty(_param.data, nodeName, lh().release());
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" skottie::PropertyObserver* C_RustPropertyObserver_New(const RustPropertyObserver::Param* param) {
+    return new RustPropertyObserver(*param);
+}
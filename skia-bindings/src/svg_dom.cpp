@@ -0,0 +1,31 @@
+#include "bindings.h"
+#include "include/core/SkCanvas.h"
+#include "include/core/SkFontMgr.h"
+#include "include/core/SkStream.h"
+#include "modules/skresources/include/SkResources.h"
+#include "modules/svg/include/SkSVGDOM.h"
+
+//
+// modules/svg/include/SkSVGDOM.h
+//
+
+extern "C" SkSVGDOM* C_SkSVGDOM_MakeFromStream(SkStream* stream, SkFontMgr* fontMgr, skresources::ResourceProvider* resourceProvider) {
+    auto builder = SkSVGDOM::Builder();
+    builder.setFontManager(fontMgr ? sp(fontMgr) : SkFontMgr::RefDefault());
+    if (resourceProvider) {
+        builder.setResourceProvider(sp(resourceProvider));
+    }
+    return builder.make(*stream).release();
+}
+
+extern "C" void C_SkSVGDOM_containerSize(const SkSVGDOM* self, SkSize* size) {
+    *size = self->containerSize();
+}
+
+extern "C" void C_SkSVGDOM_setContainerSize(SkSVGDOM* self, const SkSize* size) {
+    self->setContainerSize(*size);
+}
+
+extern "C" void C_SkSVGDOM_render(const SkSVGDOM* self, SkCanvas* canvas) {
+    self->render(canvas);
+}
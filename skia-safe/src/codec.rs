@@ -1,7 +1,14 @@
-// TODO: wrap SkAndroidCodec.h, SkCodecAnimation.h
-
 mod _codec;
 pub use _codec::*;
 
+mod android_codec;
+pub use android_codec::*;
+
+mod animated_image;
+pub use animated_image::*;
+
+mod codec_animation;
+pub use codec_animation::*;
+
 mod encoded_origin;
 pub use encoded_origin::*;
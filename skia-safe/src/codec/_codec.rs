@@ -1,9 +1,11 @@
+use super::{Blend, DisposalMethod};
+use crate::interop::OwnedRustStream;
 use crate::{prelude::*, yuva_pixmap_info::SupportedDataTypes, Image, YUVAPixmapInfo, YUVAPixmaps};
-use crate::{Data, EncodedImageFormat, EncodedOrigin, IRect, ISize, ImageInfo, Pixmap};
+use crate::{AlphaType, Data, EncodedImageFormat, EncodedOrigin, IRect, ISize, ImageInfo, Pixmap};
 use ffi::CStr;
 use skia_bindings as sb;
-use skia_bindings::{SkCodec, SkCodec_Options, SkRefCntBase};
-use std::{ffi, fmt, mem, ptr};
+use skia_bindings::{SkCodec, SkCodec_FrameInfo, SkCodec_Options, SkRefCntBase, SkStream};
+use std::{ffi, fmt, io, mem, ptr};
 
 pub use sb::SkCodec_Result as Result;
 
@@ -29,6 +31,50 @@ pub struct Options {
 
 pub use sb::SkCodec_SkScanlineOrder as ScanlineOrder;
 
+/// Information about a single frame of an animated image.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct FrameInfo {
+    // The index of the frame that this frame needs to be blended with, or `-1`, see
+    // [FrameInfo::required_frame()].
+    required_frame: i32,
+    /// Number of milliseconds to show this frame.
+    pub duration: i32,
+    /// Whether the end marker for this frame is contained in the stream.
+    pub fully_received: bool,
+    /// This is conservative; it will still return non-opaque if e.g. a color index-based frame
+    /// has a color with alpha but does not use it.
+    pub alpha_type: AlphaType,
+    /// Whether the updated rectangle contains alpha.
+    pub has_alpha_within_bounds: bool,
+    /// How this frame should be modified before decoding the next one.
+    pub disposal_method: DisposalMethod,
+    /// How this frame should blend with the prior frame.
+    pub blend: Blend,
+    /// The rectangle updated by this frame.
+    pub frame_rect: IRect,
+}
+
+impl NativeTransmutable<SkCodec_FrameInfo> for FrameInfo {}
+
+#[test]
+fn test_frame_info_layout() {
+    FrameInfo::test_layout()
+}
+
+impl FrameInfo {
+    /// The frame that this frame needs to be blended with, or `None` if it can be decoded
+    /// independently.
+    pub fn required_frame(&self) -> Option<usize> {
+        const NO_FRAME: i32 = -1;
+        if self.required_frame != NO_FRAME {
+            Some(self.required_frame.try_into().unwrap())
+        } else {
+            None
+        }
+    }
+}
+
 pub type Codec = RCHandle<SkCodec>;
 
 impl NativeBase<SkRefCntBase> for SkCodec {}
@@ -52,9 +98,29 @@ impl fmt::Debug for Codec {
 }
 
 impl Codec {
-    // TODO: wrap MakeFromStream
     // TODO: wrap from_data with SkPngChunkReader
 
+    /// Creates a codec that decodes the image read from `stream`.
+    ///
+    /// The codec takes ownership of the stream and reads from it on demand, so images can be
+    /// decoded without loading the complete encoded file into memory. Only the beginning of the
+    /// stream is buffered to detect the format, so the stream can not be rewound and the image
+    /// can be decoded only once. Use [Codec::from_seekable_stream()] to decode it again.
+    pub fn from_stream(stream: impl io::Read + Send + 'static) -> Option<Codec> {
+        Codec::from_ptr(unsafe {
+            sb::C_SkCodec_MakeFromStream(buffered_stream_ptr(OwnedRustStream::new(stream)))
+        })
+    }
+
+    /// Creates a codec that decodes the image read from `stream`, which can be rewound and
+    /// seeked by the codec.
+    pub fn from_seekable_stream(
+        stream: impl io::Read + io::Seek + Send + 'static,
+    ) -> Option<Codec> {
+        let stream = OwnedRustStream::new_seekable(stream);
+        Codec::from_ptr(unsafe { sb::C_SkCodec_MakeFromStream(stream.into_stream_ptr()) })
+    }
+
     pub fn from_data(data: impl Into<Data>) -> Option<Codec> {
         Codec::from_ptr(unsafe { sb::C_SkCodec_MakeFromData(data.into().into_ptr()) })
     }
@@ -73,7 +139,11 @@ impl Codec {
         IRect::from_native_c(unsafe { sb::C_SkCodec_bounds(self.native()) })
     }
 
-    // TODO: getICCProfile
+    /// The ICC profile embedded in the encoded image, or `None` if the image does not contain
+    /// one.
+    pub fn icc_profile(&self) -> Option<Data> {
+        Data::from_ptr(unsafe { sb::C_SkCodec_getICCProfile(self.native()) })
+    }
 
     pub fn origin(&self) -> EncodedOrigin {
         EncodedOrigin::from_native_c(unsafe { sb::C_SkCodec_getOrigin(self.native()) })
@@ -276,8 +346,24 @@ impl Codec {
             .unwrap()
     }
 
-    // TODO: FrameInfo
-    // TODO: getFrameInfo
+    /// Returns information about the frame at `index`, or `None` if `index` is out of range or
+    /// the image is not animated.
+    pub fn frame_info(&self, index: usize) -> Option<FrameInfo> {
+        let index = index.try_into().ok()?;
+        FrameInfo::try_construct(|info| unsafe {
+            sb::C_SkCodec_getFrameInfo(self.native(), index, info)
+        })
+    }
+
+    /// Returns information about all the frames of an animated image.
+    ///
+    /// As it is possible for the frame count to change as more data is received, the returned
+    /// infos may not cover all the frames of an incomplete image.
+    pub fn frame_infos(&mut self) -> Vec<FrameInfo> {
+        (0..self.get_frame_count())
+            .filter_map(|index| self.frame_info(index))
+            .collect()
+    }
 
     pub fn get_repetition_count(&mut self) -> Option<usize> {
         const REPETITION_COUNT_INFINITE: i32 = -1;
@@ -292,6 +378,12 @@ impl Codec {
     // TODO: Register
 }
 
+/// Returns a native stream that buffers the beginning of `stream`, so that the codecs can detect
+/// the format of streams that can not be rewound.
+pub(crate) fn buffered_stream_ptr(stream: OwnedRustStream) -> *mut SkStream {
+    unsafe { sb::C_SkCodec_NewBufferedStream(stream.into_stream_ptr()) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Result, ScanlineOrder, SelectionPolicy, ZeroInitialized};
@@ -0,0 +1,226 @@
+use super::{buffered_stream_ptr, Result, ZeroInitialized};
+use crate::interop::OwnedRustStream;
+use crate::prelude::*;
+use crate::{
+    AlphaType, Bitmap, ColorSpace, ColorType, Data, EncodedImageFormat, IRect, ISize, Image,
+    ImageInfo,
+};
+use skia_bindings as sb;
+use skia_bindings::SkAndroidCodec;
+use std::{fmt, io, slice};
+
+/// Options for [AndroidCodec::get_android_pixels()].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AndroidOptions {
+    pub zero_initialized: ZeroInitialized,
+    /// Decodes only this subset of the image, see [AndroidCodec::get_supported_subset()].
+    pub subset: Option<IRect>,
+    /// Decodes only every `sample_size`th pixel in both directions, for example, a sample size
+    /// of `2` decodes an image to half of its width and height.
+    pub sample_size: usize,
+}
+
+impl Default for AndroidOptions {
+    fn default() -> Self {
+        AndroidOptions {
+            zero_initialized: ZeroInitialized::No,
+            subset: None,
+            sample_size: 1,
+        }
+    }
+}
+
+/// A codec that supports decoding images scaled down by a sample size, and subsets of them.
+pub type AndroidCodec = RefHandle<SkAndroidCodec>;
+
+impl NativeDrop for SkAndroidCodec {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkAndroidCodec_delete(self) }
+    }
+}
+
+impl fmt::Debug for AndroidCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AndroidCodec")
+            .field("info", &self.info())
+            .field("encoded_format", &self.encoded_format())
+            .finish()
+    }
+}
+
+impl AndroidCodec {
+    pub fn from_data(data: impl Into<Data>) -> Option<AndroidCodec> {
+        AndroidCodec::from_ptr(unsafe { sb::C_SkAndroidCodec_MakeFromData(data.into().into_ptr()) })
+    }
+
+    /// Creates a codec that decodes the image read from `stream`.
+    ///
+    /// Only the beginning of the stream is buffered to detect the format, so the image can be
+    /// decoded only once, see [crate::Codec::from_stream()].
+    pub fn from_stream(stream: impl io::Read + Send + 'static) -> Option<AndroidCodec> {
+        let stream = buffered_stream_ptr(OwnedRustStream::new(stream));
+        AndroidCodec::from_ptr(unsafe { sb::C_SkAndroidCodec_MakeFromStream(stream) })
+    }
+
+    /// Creates a codec that decodes the image read from `stream`, which can be rewound and
+    /// seeked by the codec.
+    pub fn from_seekable_stream(
+        stream: impl io::Read + io::Seek + Send + 'static,
+    ) -> Option<AndroidCodec> {
+        let stream = OwnedRustStream::new_seekable(stream);
+        AndroidCodec::from_ptr(unsafe {
+            sb::C_SkAndroidCodec_MakeFromStream(stream.into_stream_ptr())
+        })
+    }
+
+    pub fn info(&self) -> ImageInfo {
+        let mut info = ImageInfo::default();
+        unsafe { sb::C_SkAndroidCodec_getInfo(self.native(), info.native_mut()) };
+        info
+    }
+
+    /// The ICC profile embedded in the encoded image, see [crate::Codec::icc_profile()].
+    pub fn icc_profile(&self) -> Option<Data> {
+        Data::from_ptr(unsafe { sb::C_SkAndroidCodec_getICCProfile(self.native()) })
+    }
+
+    pub fn encoded_format(&self) -> EncodedImageFormat {
+        unsafe { sb::C_SkAndroidCodec_getEncodedFormat(self.native()) }
+    }
+
+    /// Returns the color type to decode to, given the `requested_color_type`.
+    pub fn compute_output_color_type(&mut self, requested_color_type: ColorType) -> ColorType {
+        ColorType::from_native_c(unsafe {
+            sb::C_SkAndroidCodec_computeOutputColorType(
+                self.native_mut(),
+                requested_color_type.into_native(),
+            )
+        })
+    }
+
+    /// Returns the alpha type to decode to.
+    pub fn compute_output_alpha_type(&mut self, requested_unpremul: bool) -> AlphaType {
+        unsafe {
+            sb::C_SkAndroidCodec_computeOutputAlphaType(self.native_mut(), requested_unpremul)
+        }
+    }
+
+    /// Returns the color space to decode to, given the `output_color_type` and the preferred
+    /// color space.
+    pub fn compute_output_color_space(
+        &mut self,
+        output_color_type: ColorType,
+        pref_color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<ColorSpace> {
+        ColorSpace::from_ptr(unsafe {
+            sb::C_SkAndroidCodec_computeOutputColorSpace(
+                self.native_mut(),
+                output_color_type.into_native(),
+                pref_color_space.into().into_ptr_or_null(),
+            )
+        })
+    }
+
+    /// Returns the largest sample size that decodes the image to at least `desired_size`, and
+    /// the dimensions of the image decoded with it.
+    pub fn compute_sample_size(&self, desired_size: impl Into<ISize>) -> (usize, ISize) {
+        let mut size = desired_size.into();
+        let sample_size =
+            unsafe { sb::C_SkAndroidCodec_computeSampleSize(self.native(), size.native_mut()) };
+        (sample_size.try_into().unwrap(), size)
+    }
+
+    /// Returns the dimensions of the image decoded with `sample_size`.
+    pub fn get_sampled_dimensions(&self, sample_size: usize) -> ISize {
+        ISize::from_native_c(unsafe {
+            sb::C_SkAndroidCodec_getSampledDimensions(
+                self.native(),
+                sample_size.try_into().unwrap(),
+            )
+        })
+    }
+
+    /// Returns the supported subset that is closest to `desired_subset`, or `None` if the
+    /// codec does not support subset decoding or the subset is not inside the image.
+    pub fn get_supported_subset(&self, desired_subset: impl AsRef<IRect>) -> Option<IRect> {
+        let mut subset = *desired_subset.as_ref();
+        unsafe { sb::C_SkAndroidCodec_getSupportedSubset(self.native(), subset.native_mut()) }
+            .if_true_some(subset)
+    }
+
+    /// Returns the dimensions of `subset` decoded with `sample_size`.
+    pub fn get_sampled_subset_dimensions(
+        &self,
+        sample_size: usize,
+        subset: impl AsRef<IRect>,
+    ) -> ISize {
+        ISize::from_native_c(unsafe {
+            sb::C_SkAndroidCodec_getSampledSubsetDimensions(
+                self.native(),
+                sample_size.try_into().unwrap(),
+                subset.as_ref().native(),
+            )
+        })
+    }
+
+    /// Decodes the image into `pixels`.
+    ///
+    /// The dimensions of `info` must match the sampled dimensions of the image or its subset.
+    pub fn get_android_pixels<'a>(
+        &mut self,
+        info: &ImageInfo,
+        pixels: &mut [u8],
+        row_bytes: usize,
+        options: impl Into<Option<&'a AndroidOptions>>,
+    ) -> Result {
+        if !info.valid_pixels(row_bytes, pixels) {
+            return Result::InvalidParameters;
+        }
+        let mut options = options.into().copied().unwrap_or_default();
+        unsafe {
+            sb::C_SkAndroidCodec_getAndroidPixels(
+                self.native_mut(),
+                info.native(),
+                pixels.as_mut_ptr() as _,
+                row_bytes,
+                options.zero_initialized,
+                options.subset.native_mut().as_ptr_or_null_mut(),
+                options.sample_size.try_into().unwrap(),
+            )
+        }
+    }
+
+    /// Decodes the image with `sample_size` into a raster [Image] of `color_type`.
+    pub fn get_android_image(
+        &mut self,
+        color_type: ColorType,
+        sample_size: usize,
+    ) -> Option<Image> {
+        let dimensions = self.get_sampled_dimensions(sample_size);
+        let color_type = self.compute_output_color_type(color_type);
+        let alpha_type = self.compute_output_alpha_type(false);
+        let color_space = self.compute_output_color_space(color_type, None);
+        let info = ImageInfo::new(dimensions, color_type, alpha_type, color_space);
+        // Decode into the bitmap's pixels and share them with the image, so that the decoded
+        // image is held in memory only once.
+        let mut bitmap = Bitmap::new();
+        if !bitmap.try_alloc_pixels_info(&info, None) {
+            return None;
+        }
+        let row_bytes = bitmap.row_bytes();
+        let pixels = unsafe {
+            slice::from_raw_parts_mut(bitmap.pixels() as *mut u8, bitmap.compute_byte_size())
+        };
+        let options = AndroidOptions {
+            sample_size,
+            ..Default::default()
+        };
+        match self.get_android_pixels(&info, pixels, row_bytes, &options) {
+            Result::Success | Result::IncompleteInput => {
+                bitmap.set_immutable();
+                Some(bitmap.as_image())
+            }
+            _ => None,
+        }
+    }
+}
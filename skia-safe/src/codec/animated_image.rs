@@ -0,0 +1,120 @@
+use crate::interop::OwnedRustStream;
+use crate::prelude::*;
+use crate::{Data, Drawable, Image};
+use skia_bindings as sb;
+use skia_bindings::{SkAnimatedImage, SkDrawable, SkRefCntBase};
+use std::{fmt, io};
+
+/// Plays back an animated image, like an animated GIF or WebP, by decoding one frame after the
+/// other.
+///
+/// An [AnimatedImage] is a [Drawable] that draws the current frame, see
+/// [AnimatedImage::to_drawable()].
+pub type AnimatedImage = RCHandle<SkAnimatedImage>;
+
+impl NativeRefCountedBase for SkAnimatedImage {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for AnimatedImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnimatedImage")
+            .field("is_finished", &self.is_finished())
+            .field("frame_count", &self.frame_count())
+            .field("repetition_count", &self.repetition_count())
+            .finish()
+    }
+}
+
+impl AnimatedImage {
+    /// Decodes the first frame of the animated image in `data`.
+    pub fn from_data(data: impl Into<Data>) -> Option<AnimatedImage> {
+        AnimatedImage::from_ptr(unsafe {
+            sb::C_SkAnimatedImage_MakeFromData(data.into().into_ptr())
+        })
+    }
+
+    /// Decodes the first frame of the animated image read from `stream`.
+    ///
+    /// The image takes ownership of the stream and rewinds it when frames need to be decoded
+    /// again.
+    pub fn from_stream(stream: impl io::Read + io::Seek + Send + 'static) -> Option<AnimatedImage> {
+        let stream = OwnedRustStream::new_seekable(stream);
+        AnimatedImage::from_ptr(unsafe {
+            sb::C_SkAnimatedImage_MakeFromStream(stream.into_stream_ptr())
+        })
+    }
+
+    /// Returns a [Drawable] that shares this image and draws its current frame.
+    pub fn to_drawable(&self) -> Drawable {
+        Drawable::from_unshared_ptr(unsafe { self.native_mut_force() } as *mut SkDrawable).unwrap()
+    }
+
+    /// Resets the animation to the beginning.
+    pub fn reset(&mut self) {
+        unsafe { sb::C_SkAnimatedImage_reset(self.native_mut()) }
+    }
+
+    /// Whether the animation completed.
+    ///
+    /// Animations that repeat infinitely never finish.
+    pub fn is_finished(&self) -> bool {
+        unsafe { sb::C_SkAnimatedImage_isFinished(self.native()) }
+    }
+
+    /// Decodes the next frame.
+    ///
+    /// Returns the number of milliseconds until the frame after it should be shown, or `None`
+    /// if the animation is finished.
+    pub fn decode_next_frame(&mut self) -> Option<usize> {
+        const FINISHED: i32 = -1;
+        let duration = unsafe { sb::C_SkAnimatedImage_decodeNextFrame(self.native_mut()) };
+        if duration != FINISHED {
+            Some(duration.try_into().unwrap())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the current frame as an [Image].
+    pub fn current_frame(&mut self) -> Option<Image> {
+        Image::from_ptr(unsafe { sb::C_SkAnimatedImage_getCurrentFrame(self.native_mut()) })
+    }
+
+    /// The number of milliseconds the current frame is shown.
+    pub fn current_frame_duration(&mut self) -> usize {
+        unsafe { sb::C_SkAnimatedImage_currentFrameDuration(self.native_mut()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// Sets how many times the animation repeats after it was shown once, `None` repeats it
+    /// infinitely.
+    pub fn set_repetition_count(&mut self, count: impl Into<Option<usize>>) -> &mut Self {
+        const REPETITION_COUNT_INFINITE: i32 = -1;
+        let count = count
+            .into()
+            .map(|count| count.try_into().unwrap())
+            .unwrap_or(REPETITION_COUNT_INFINITE);
+        unsafe { sb::C_SkAnimatedImage_setRepetitionCount(self.native_mut(), count) }
+        self
+    }
+
+    /// How many times the animation repeats after it was shown once, `None` if it repeats
+    /// infinitely.
+    pub fn repetition_count(&self) -> Option<usize> {
+        const REPETITION_COUNT_INFINITE: i32 = -1;
+        let count = unsafe { sb::C_SkAnimatedImage_getRepetitionCount(self.native()) };
+        if count != REPETITION_COUNT_INFINITE {
+            Some(count.try_into().unwrap())
+        } else {
+            None
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        unsafe { sb::C_SkAnimatedImage_getFrameCount(self.native()) }
+            .try_into()
+            .unwrap()
+    }
+}
@@ -0,0 +1,13 @@
+/// How to dispose of a frame of an animated image before the next frame is drawn.
+pub use skia_bindings::SkCodecAnimation_DisposalMethod as DisposalMethod;
+#[test]
+fn test_disposal_method_naming() {
+    let _ = DisposalMethod::RestorePrevious;
+}
+
+/// How a frame of an animated image is blended with the prior frame.
+pub use skia_bindings::SkCodecAnimation_Blend as Blend;
+#[test]
+fn test_blend_naming() {
+    let _ = Blend::SrcOver;
+}
@@ -53,7 +53,7 @@ pub mod document;
 pub use document::Document;
 
 pub mod drawable;
-pub use drawable::Drawable;
+pub use drawable::{Drawable, DrawableImpl};
 
 mod encoded_image_format;
 pub use encoded_image_format::*;
@@ -107,6 +107,9 @@ pub use image_generator::*;
 mod image_info;
 pub use image_info::*;
 
+mod jpeg_encoder;
+pub use jpeg_encoder::*;
+
 mod m44;
 pub use m44::*;
 
@@ -157,6 +160,9 @@ pub use pixel_ref::*;
 mod pixmap;
 pub use pixmap::*;
 
+mod png_encoder;
+pub use png_encoder::*;
+
 mod point;
 pub use point::*;
 
@@ -178,6 +184,9 @@ pub use region::Region;
 pub mod rrect;
 pub use rrect::RRect;
 
+mod row_encoder;
+pub use row_encoder::*;
+
 mod rsxform;
 pub use rsxform::*;
 
@@ -190,6 +199,9 @@ pub use sampling_options::{
 mod scalar_;
 pub use scalar_::*;
 
+mod serial_procs;
+pub use serial_procs::*;
+
 pub mod shader;
 pub use shader::{shaders, Shader};
 
@@ -235,6 +247,11 @@ pub use un_pre_multiply::*;
 pub mod vertices;
 pub use vertices::Vertices;
 
+#[cfg(feature = "webp-encode")]
+mod webp_encoder;
+#[cfg(feature = "webp-encode")]
+pub use webp_encoder::*;
+
 pub mod yuva_info;
 pub use yuva_info::YUVAInfo;
 
@@ -1,17 +1,126 @@
 use crate::prelude::*;
-use skia_bindings::{SkBBHFactory, SkBBoxHierarchy};
+use crate::Rect;
+use skia_bindings::{self as sb, SkBBHFactory, SkBBoxHierarchy, SkRefCntBase};
+use std::{ffi, fmt, os::raw};
 
-// TODO: complete the wrapper
+/// A spatial index of the bounds of the drawing operations in a picture.
+///
+/// Pictures that are recorded with a bounding box hierarchy only play back the operations that
+/// intersect the clip of the canvas they are drawn into, see
+/// [crate::PictureRecorder::begin_recording_with_bbh()].
 pub type BBoxHierarchy = RCHandle<SkBBoxHierarchy>;
 
-// TODO: complete the wrapper
-pub type BBHFactory = Handle<SkBBHFactory>;
+impl NativeRefCountedBase for SkBBoxHierarchy {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for BBoxHierarchy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BBoxHierarchy")
+            .field("bytes_used", &self.bytes_used())
+            .finish()
+    }
+}
+
+impl BBoxHierarchy {
+    /// Creates an empty R-tree.
+    pub fn new_rtree() -> BBoxHierarchy {
+        BBHFactory::new_rtree().make_bbh()
+    }
+
+    /// Inserts the bounds of `rects.len()` operations, the index of a rect in `rects` is the
+    /// index of the operation.
+    ///
+    /// # Safety
+    ///
+    /// The hierarchy must be empty and must not be shared with a [crate::PictureRecorder]: it
+    /// is filled only once, and pictures that are played back with it draw the operations at
+    /// the indices it returns.
+    pub unsafe fn insert(&mut self, rects: &[Rect]) {
+        sb::C_SkBBoxHierarchy_insert(
+            self.native_mut(),
+            rects.native().as_ptr(),
+            rects.len().try_into().unwrap(),
+        )
+    }
+
+    /// Returns the indices of the operations whose bounds intersect `query`, in ascending order.
+    pub fn search(&self, query: impl AsRef<Rect>) -> Vec<usize> {
+        let mut results: Vec<usize> = Vec::new();
+        unsafe {
+            sb::C_SkBBoxHierarchy_search(
+                self.native(),
+                query.as_ref().native(),
+                &mut results as *mut Vec<usize> as *mut ffi::c_void,
+                Some(push_search_result),
+            )
+        }
+        results
+    }
+
+    pub fn bytes_used(&self) -> usize {
+        unsafe { sb::C_SkBBoxHierarchy_bytesUsed(self.native()) }
+    }
+}
+
+unsafe extern "C" fn push_search_result(results: *mut ffi::c_void, index: raw::c_int) {
+    let results = &mut *(results as *mut Vec<usize>);
+    results.push(index.try_into().unwrap());
+}
+
+/// Creates a [BBoxHierarchy] for each picture that is recorded with it, see
+/// [crate::PictureRecorder::begin_recording()].
+pub type BBHFactory = RefHandle<SkBBHFactory>;
 
 impl NativeDrop for SkBBHFactory {
     fn drop(&mut self) {
-        unimplemented!()
+        unsafe { sb::C_SkBBHFactory_delete(self) }
+    }
+}
+
+impl fmt::Debug for BBHFactory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BBHFactory").finish()
+    }
+}
+
+impl BBHFactory {
+    /// Creates a factory for R-trees.
+    pub fn new_rtree() -> BBHFactory {
+        BBHFactory::from_ptr(unsafe { sb::C_SkRTreeFactory_new() }).unwrap()
+    }
+
+    pub fn make_bbh(&self) -> BBoxHierarchy {
+        BBoxHierarchy::from_ptr(unsafe { sb::C_SkBBHFactory_makeBBH(self.native()) }).unwrap()
     }
 }
 
-// TODO: complete the wrapper functions
-impl BBHFactory {}
+#[cfg(test)]
+mod tests {
+    use super::{BBHFactory, BBoxHierarchy};
+    use crate::Rect;
+
+    #[test]
+    fn rtree_search() {
+        let mut rtree = BBoxHierarchy::new_rtree();
+        let rects: Vec<Rect> = (0..100)
+            .map(|i| Rect::from_xywh((i % 10) as f32 * 10.0, (i / 10) as f32 * 10.0, 5.0, 5.0))
+            .collect();
+        unsafe { rtree.insert(&rects) };
+        assert_eq!(
+            rtree.search(Rect::from_xywh(0.0, 0.0, 12.0, 12.0)),
+            [0, 1, 10, 11]
+        );
+        assert!(rtree
+            .search(Rect::from_xywh(200.0, 200.0, 10.0, 10.0))
+            .is_empty());
+        assert!(rtree.bytes_used() > 0);
+    }
+
+    #[test]
+    fn factory_makes_empty_hierarchies() {
+        let factory = BBHFactory::new_rtree();
+        let bbh = factory.make_bbh();
+        assert!(bbh.search(Rect::from_wh(100.0, 100.0)).is_empty());
+    }
+}
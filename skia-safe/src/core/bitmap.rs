@@ -422,7 +422,44 @@ impl Bitmap {
             .expect("Bitmap::alloc_n32_pixels_failed")
     }
 
-    // TODO: wrap installPixels with releaseProc.
+    /// Sets [ImageInfo] to info following the rules in `set_info()`, and creates [PixelRef]
+    /// that takes ownership of `pixels`. `pixels` are dropped when the [PixelRef] is released.
+    ///
+    /// `row_bytes` defaults to `info.min_row_bytes()`.
+    ///
+    /// If [ImageInfo] could not be set, `row_bytes` is less than `info.min_row_bytes()`, or
+    /// `pixels` are too small to hold the pixels described by `info`: drops `pixels`, calls
+    /// `reset()`, and returns `false`.
+    pub fn install_pixels_owned<P>(
+        &mut self,
+        info: &ImageInfo,
+        pixels: P,
+        row_bytes: impl Into<Option<usize>>,
+    ) -> bool
+    where
+        P: AsMut<[u8]> + Send + 'static,
+    {
+        let row_bytes = row_bytes.into().unwrap_or_else(|| info.min_row_bytes());
+        let mut pixels = Box::new(pixels);
+        let addr = {
+            let pixels = (*pixels).as_mut();
+            if pixels.len() < info.compute_byte_size(row_bytes) {
+                self.reset();
+                return false;
+            }
+            pixels.as_mut_ptr()
+        };
+        // installPixels() calls the release proc if it fails, too.
+        unsafe {
+            self.native_mut().installPixels(
+                info.native(),
+                addr as _,
+                row_bytes,
+                Some(release_owned_pixels::<P>),
+                Box::into_raw(pixels) as _,
+            )
+        }
+    }
 
     /// Sets [ImageInfo] to info following the rules in `set_info()`, and creates [PixelRef]
     /// containing `pixels` and `row_bytes`.
@@ -467,7 +504,33 @@ impl Bitmap {
             .expect("Bitmap::alloc_pixels failed")
     }
 
-    // TODO: allocPixels(Allocator*)
+    /// Allocates pixel memory with `allocator`, and replaces existing [PixelRef]. The allocation
+    /// size is determined by [ImageInfo] width, height, and [ColorType].
+    ///
+    /// Returns `false` if `allocator` fails.
+    #[must_use]
+    pub fn try_alloc_pixels_with_allocator<A: BitmapAllocator>(
+        &mut self,
+        allocator: &mut A,
+    ) -> bool {
+        unsafe {
+            sb::C_SkBitmap_tryAllocPixelsWithAllocator(
+                self.native_mut(),
+                allocator as *mut A as _,
+                Some(alloc_pixel_ref::<A>),
+            )
+        }
+    }
+
+    /// Allocates pixel memory with `allocator`, and replaces existing [PixelRef]. The allocation
+    /// size is determined by [ImageInfo] width, height, and [ColorType].
+    ///
+    /// Aborts if `allocator` fails.
+    pub fn alloc_pixels_with_allocator(&mut self, allocator: &mut impl BitmapAllocator) {
+        self.try_alloc_pixels_with_allocator(allocator)
+            .into_option()
+            .expect("Bitmap::alloc_pixels_with_allocator failed")
+    }
 
     // TODO: find a way to return pixel ref without increasing the ref count here?
 
@@ -724,10 +787,41 @@ impl Bitmap {
     }
 }
 
+/// Allocates the pixel memory of a [Bitmap], see [Bitmap::try_alloc_pixels_with_allocator()].
+pub trait BitmapAllocator {
+    /// Allocates pixel memory for the [ImageInfo] and row bytes of `bitmap`, and installs it with
+    /// [Bitmap::set_pixel_ref()] or [Bitmap::install_pixels_owned()].
+    ///
+    /// Returns `false` if the pixel memory could not be allocated.
+    fn alloc_pixel_ref(&mut self, bitmap: &mut Bitmap) -> bool;
+}
+
+unsafe extern "C" fn alloc_pixel_ref<A: BitmapAllocator>(
+    data: *mut ffi::c_void,
+    bitmap: *mut SkBitmap,
+) -> bool {
+    let allocator = &mut *(data as *mut A);
+    allocator.alloc_pixel_ref(Bitmap::from_native_ref_mut(&mut *bitmap))
+}
+
+/// A pixel release proc that drops the pixels of the type `P` that are passed as the context.
+pub(crate) unsafe extern "C" fn release_owned_pixels<P>(
+    _pixels: *mut ffi::c_void,
+    context: *mut ffi::c_void,
+) {
+    drop(Box::from_raw(context as *mut P))
+}
+
 #[cfg(test)]
 mod tests {
     use super::TileMode;
-    use crate::{Bitmap, SamplingOptions};
+    use crate::{
+        AlphaType, Bitmap, BitmapAllocator, Color, ColorType, ImageInfo, PixelRef, SamplingOptions,
+    };
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
 
     #[test]
     fn create_clone_and_drop() {
@@ -769,4 +863,73 @@ mod tests {
         let bm = Bitmap::new();
         let _ = bm.pixel_ref_origin();
     }
+
+    struct DropFlag(Vec<u8>, Arc<AtomicBool>);
+
+    impl AsMut<[u8]> for DropFlag {
+        fn as_mut(&mut self) -> &mut [u8] {
+            &mut self.0
+        }
+    }
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.1.store(true, Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn install_pixels_owned() {
+        let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let dropped = Arc::new(AtomicBool::new(false));
+        let mut bitmap = Bitmap::new();
+        assert!(bitmap.install_pixels_owned(
+            &info,
+            DropFlag(vec![0xff; 16], dropped.clone()),
+            None
+        ));
+        assert_eq!(bitmap.get_color((1, 1)), Color::WHITE);
+        assert!(!dropped.load(Ordering::SeqCst));
+        drop(bitmap);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn install_pixels_owned_drops_too_small_pixels() {
+        let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let dropped = Arc::new(AtomicBool::new(false));
+        let mut bitmap = Bitmap::new();
+        assert!(!bitmap.install_pixels_owned(&info, DropFlag(vec![0; 15], dropped.clone()), None));
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[derive(Default)]
+    struct CountingAllocator {
+        allocations: usize,
+    }
+
+    impl BitmapAllocator for CountingAllocator {
+        fn alloc_pixel_ref(&mut self, bitmap: &mut Bitmap) -> bool {
+            let pixels = vec![0u8; bitmap.compute_byte_size()];
+            match PixelRef::new_owned(bitmap.info(), pixels, bitmap.row_bytes()) {
+                Some(pixel_ref) => {
+                    bitmap.set_pixel_ref(pixel_ref, (0, 0));
+                    self.allocations += 1;
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    #[test]
+    fn alloc_pixels_with_allocator() {
+        let mut allocator = CountingAllocator::default();
+        let mut bitmap = Bitmap::new();
+        assert!(bitmap.set_info(&ImageInfo::new_n32_premul((4, 4), None), None));
+        bitmap.alloc_pixels_with_allocator(&mut allocator);
+        assert_eq!(allocator.allocations, 1);
+        bitmap.erase_color(Color::GREEN);
+        assert_eq!(bitmap.get_color((3, 3)), Color::GREEN);
+    }
 }
@@ -1,10 +1,12 @@
+use super::raster_handle_allocator::native_raster_handle_allocator;
 #[cfg(feature = "gpu")]
 use crate::gpu;
 use crate::{
     prelude::*, scalar, u8cpu, Bitmap, BlendMode, ClipOp, Color, Color4f, Data, Drawable,
     FilterMode, Font, IPoint, IRect, ISize, Image, ImageFilter, ImageInfo, Matrix, Paint, Path,
-    Picture, Pixmap, Point, QuickReject, RRect, Rect, Region, SamplingOptions, Shader, Surface,
-    SurfaceProps, TextBlob, TextEncoding, Vector, Vertices, M44,
+    Picture, Pixmap, Point, QuickReject, RRect, RSXform, RasterHandle, RasterHandleAllocator, Rect,
+    Region, SamplingOptions, Shader, Surface, SurfaceProps, TextBlob, TextEncoding, Vector,
+    Vertices, M44,
 };
 use skia_bindings as sb;
 use skia_bindings::{
@@ -320,6 +322,21 @@ impl Canvas {
         }
     }
 
+    /// Allocates raster [`Canvas`] whose layers are allocated by `allocator`, together with a
+    /// [`RasterHandle`] another graphics library uses to draw into the same pixels.
+    ///
+    /// The canvas takes ownership of `allocator`. Returns `None` if the base layer can not be
+    /// allocated.
+    pub fn from_raster_handle_allocator(
+        allocator: impl RasterHandleAllocator + 'static,
+        info: &ImageInfo,
+    ) -> Option<OwnedCanvas<'static>> {
+        let param = native_raster_handle_allocator(allocator);
+        Self::own_from_native_ptr(unsafe {
+            sb::C_SkRasterHandleAllocator_MakeCanvas(&param, info.native())
+        })
+    }
+
     /// Allocates raster [`Canvas`] specified by inline image specification. Subsequent [`Canvas`]
     /// calls draw into pixels.
     /// [`crate::ColorType`] is set to [`crate::ColorType::n32()`].
@@ -545,7 +562,14 @@ impl Canvas {
         }
     }
 
-    // TODO: accessTopRasterHandle()
+    /// Returns the [`RasterHandle`] of the top layer, after it was updated to the current matrix
+    /// and clip, see [`RasterHandleAllocator::update_handle()`].
+    ///
+    /// Returns `None` if the canvas was not created by [`Self::from_raster_handle_allocator()`].
+    pub fn access_top_raster_handle(&self) -> Option<RasterHandle> {
+        let handle = unsafe { sb::C_SkCanvas_accessTopRasterHandle(self.native()) };
+        (!handle.is_null()).if_true_some(handle)
+    }
 
     /// Returns `true` if [`Canvas`] has direct access to its pixels.
     ///
@@ -1886,7 +1910,57 @@ impl Canvas {
         self
     }
 
-    // TODO: drawAtlas
+    /// Draws a set of sprites from `atlas`, using clip, [`Matrix`], and optional [`Paint`]
+    /// `paint`.
+    ///
+    /// For each sprite, the rectangle `tex[i]` of `atlas` is transformed by `xforms[i]` and
+    /// drawn. If `colors` is provided, each sprite's color is combined with its texture using
+    /// [`BlendMode`] `mode`.
+    ///
+    /// - `atlas` [`Image`] containing sprites
+    /// - `xforms` [`RSXform`] mappings for sprites in `atlas`
+    /// - `tex` [`Rect`] locations of sprites in `atlas`
+    /// - `colors` one per sprite, blended with sprite using [`BlendMode`]; may be `None`
+    /// - `mode` [`BlendMode`] combining colors and sprites
+    /// - `sampling` [`SamplingOptions`] used when sampling from the atlas image
+    /// - `cull` bounds of transformed sprites for efficient clipping; may be `None`
+    /// - `paint` [`crate::ColorFilter`], [`ImageFilter`], [`BlendMode`], and so on; may be
+    ///   `None`
+    ///
+    /// Panics if `xforms`, `tex`, and `colors` differ in length.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_atlas(
+        &mut self,
+        atlas: &Image,
+        xforms: &[RSXform],
+        tex: &[Rect],
+        colors: Option<&[Color]>,
+        mode: BlendMode,
+        sampling: impl Into<SamplingOptions>,
+        cull: Option<&Rect>,
+        paint: Option<&Paint>,
+    ) -> &mut Self {
+        let count = xforms.len();
+        assert_eq!(tex.len(), count);
+        if let Some(colors) = colors {
+            assert_eq!(colors.len(), count);
+        }
+        unsafe {
+            sb::C_SkCanvas_drawAtlas(
+                self.native_mut(),
+                atlas.native(),
+                xforms.native().as_ptr(),
+                tex.native().as_ptr(),
+                colors.map(|c| c.native().as_ptr()).unwrap_or(ptr::null()),
+                count.try_into().unwrap(),
+                mode,
+                sampling.into().native(),
+                cull.native_ptr_or_null(),
+                paint.native_ptr_or_null(),
+            )
+        }
+        self
+    }
 
     /// Draws [`Drawable`] drawable using clip and [`Matrix`], concatenated with
     /// optional matrix.
@@ -2217,9 +2291,11 @@ impl AutoCanvasRestore {
 #[cfg(test)]
 mod tests {
     use crate::{
-        canvas::SaveLayerFlags, canvas::SaveLayerRec, AlphaType, Canvas, ClipOp, Color, ColorType,
-        ImageInfo, OwnedCanvas, Rect,
+        canvas::SaveLayerFlags, canvas::SaveLayerRec, AlphaType, BlendMode, Canvas, ClipOp, Color,
+        Color4f, ColorType, IRect, ImageInfo, Matrix, OwnedCanvas, Paint, RSXform, RasterHandle,
+        RasterHandleAllocation, RasterHandleAllocator, Rect, SamplingOptions, Surface,
     };
+    use std::{cell::RefCell, ptr, rc::Rc};
 
     #[test]
     fn test_raster_direct_creation_and_clear_in_memory() {
@@ -2298,4 +2374,107 @@ mod tests {
         let _ = surface.canvas().local_clip_bounds();
         let _ = surface.canvas().local_to_device();
     }
+
+    struct TestAllocator {
+        updates: Rc<RefCell<Vec<(usize, IRect)>>>,
+    }
+
+    impl RasterHandleAllocator for TestAllocator {
+        fn alloc_handle(&mut self, info: &ImageInfo) -> Option<RasterHandleAllocation> {
+            let pixels = vec![0u8; info.compute_min_byte_size()];
+            let handle = (self.updates.borrow().len() + 1) as RasterHandle;
+            RasterHandleAllocation::new_owned(info, pixels, None, handle)
+        }
+
+        fn update_handle(&mut self, handle: RasterHandle, _matrix: &Matrix, clip_bounds: &IRect) {
+            self.updates
+                .borrow_mut()
+                .push((handle as usize, *clip_bounds));
+        }
+    }
+
+    #[test]
+    fn raster_handle_allocator() {
+        let updates = Rc::new(RefCell::new(Vec::new()));
+        let allocator = TestAllocator {
+            updates: updates.clone(),
+        };
+        let info = ImageInfo::new_n32_premul((10, 10), None);
+        let mut canvas = Canvas::from_raster_handle_allocator(allocator, &info).unwrap();
+        canvas.clip_rect(Rect::from_xywh(2.0, 2.0, 4.0, 4.0), None, None);
+        let handle = canvas.access_top_raster_handle().unwrap();
+        assert_eq!(handle as usize, 1);
+        assert_eq!(*updates.borrow(), vec![(1, IRect::from_xywh(2, 2, 4, 4))]);
+
+        assert!(OwnedCanvas::default().access_top_raster_handle().is_none());
+    }
+
+    struct TooSmallAllocator;
+
+    impl RasterHandleAllocator for TooSmallAllocator {
+        fn alloc_handle(&mut self, info: &ImageInfo) -> Option<RasterHandleAllocation> {
+            let info = info.with_dimensions((info.width(), info.height() / 2));
+            let pixels = vec![0u8; info.compute_min_byte_size()];
+            RasterHandleAllocation::new_owned(&info, pixels, None, ptr::null_mut())
+        }
+
+        fn update_handle(&mut self, _handle: RasterHandle, _matrix: &Matrix, _clip: &IRect) {}
+    }
+
+    #[test]
+    fn raster_handle_allocator_rejects_too_small_allocations() {
+        let info = ImageInfo::new_n32_premul((10, 10), None);
+        assert!(Canvas::from_raster_handle_allocator(TooSmallAllocator, &info).is_none());
+    }
+
+    #[test]
+    fn draw_atlas_sprites() {
+        let mut atlas = Surface::new_raster_n32_premul((4, 2)).unwrap();
+        atlas.canvas().clear(Color::RED);
+        atlas.canvas().draw_rect(
+            Rect::from_xywh(2.0, 0.0, 2.0, 2.0),
+            &Paint::new(Color4f::from(Color::BLUE), None),
+        );
+        let atlas = atlas.image_snapshot();
+
+        let mut surface = Surface::new_raster_n32_premul((4, 2)).unwrap();
+        surface.canvas().draw_atlas(
+            &atlas,
+            &[
+                RSXform::new(1.0, 0.0, (0, 0)),
+                RSXform::new(1.0, 0.0, (2, 0)),
+            ],
+            &[
+                Rect::from_xywh(2.0, 0.0, 2.0, 2.0),
+                Rect::from_xywh(0.0, 0.0, 2.0, 2.0),
+            ],
+            None,
+            BlendMode::Modulate,
+            SamplingOptions::default(),
+            None,
+            None,
+        );
+        let pixels = surface.peek_pixels().unwrap();
+        assert_eq!(pixels.get_color((0, 0)), Color::BLUE);
+        assert_eq!(pixels.get_color((3, 1)), Color::RED);
+    }
+
+    #[test]
+    #[should_panic]
+    fn draw_atlas_panics_on_length_mismatch() {
+        let atlas = Surface::new_raster_n32_premul((2, 2))
+            .unwrap()
+            .image_snapshot();
+        let mut surface = Surface::new_raster_n32_premul((2, 2)).unwrap();
+        surface.canvas().draw_atlas(
+            &atlas,
+            &[RSXform::new(1.0, 0.0, (0, 0))],
+            &[],
+            None,
+            BlendMode::Modulate,
+            SamplingOptions::default(),
+            None,
+            None,
+        );
+    }
 }
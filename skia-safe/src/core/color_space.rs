@@ -1,6 +1,8 @@
 use super::Data;
 use crate::prelude::*;
-use skia_bindings::{self as sb, SkColorSpace, SkColorSpacePrimaries};
+use skia_bindings::{
+    self as sb, skcms_Matrix3x3, skcms_TransferFunction, SkColorSpace, SkColorSpacePrimaries,
+};
 use std::fmt;
 
 #[derive(Clone, PartialEq, Debug)]
@@ -23,6 +25,7 @@ fn test_color_space_primaries_layout() {
 }
 
 #[derive(Clone, PartialEq, Debug)]
+#[repr(C)]
 pub struct ColorSpaceTransferFn {
     pub g: f32,
     pub a: f32,
@@ -33,6 +36,33 @@ pub struct ColorSpaceTransferFn {
     pub f: f32,
 }
 
+impl NativeTransmutable<skcms_TransferFunction> for ColorSpaceTransferFn {}
+#[test]
+fn test_color_space_transfer_fn_layout() {
+    ColorSpaceTransferFn::test_layout()
+}
+
+/// A 3x3 row-major matrix that maps linear RGB to another gamut, for example to XYZ D50.
+#[derive(Clone, PartialEq, Debug)]
+#[repr(C)]
+pub struct ColorSpaceXYZ {
+    pub vals: [[f32; 3]; 3],
+}
+
+impl NativeTransmutable<skcms_Matrix3x3> for ColorSpaceXYZ {}
+#[test]
+fn test_color_space_xyz_layout() {
+    ColorSpaceXYZ::test_layout()
+}
+
+impl Default for ColorSpaceXYZ {
+    fn default() -> Self {
+        ColorSpaceXYZ {
+            vals: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+}
+
 // TODO: Make the binding generator provide all these constants.
 pub mod named_transfer_fn {
     use crate::ColorSpaceTransferFn;
@@ -138,6 +168,77 @@ impl ColorSpace {
         ColorSpace::from_ptr(unsafe { sb::C_SkColorSpace_MakeSRGBLinear() }).unwrap()
     }
 
+    /// Creates a color space from a transfer function and a matrix that maps its linear RGB
+    /// values to XYZ D50.
+    ///
+    /// Returns `None` if the transfer function or the matrix are invalid.
+    pub fn new_rgb(
+        transfer_fn: &ColorSpaceTransferFn,
+        to_xyzd50: &ColorSpaceXYZ,
+    ) -> Option<ColorSpace> {
+        ColorSpace::from_ptr(unsafe {
+            sb::C_SkColorSpace_MakeRGB(transfer_fn.native(), to_xyzd50.native())
+        })
+    }
+
+    /// Creates a color space from an ICC profile.
+    ///
+    /// Returns `None` if the profile can not be parsed, or is not an RGB profile that can be
+    /// described by a transfer function and a gamut matrix.
+    pub fn from_icc(icc: &[u8]) -> Option<ColorSpace> {
+        ColorSpace::from_ptr(unsafe {
+            sb::C_SkColorSpace_MakeFromICC(icc.as_ptr() as _, icc.len())
+        })
+    }
+
+    /// Writes an ICC profile that describes this color space.
+    ///
+    /// Returns `None` if the transfer function is not numerical, like PQ or HLG.
+    pub fn to_icc(&self) -> Option<Data> {
+        Data::from_ptr(unsafe { sb::C_SkColorSpace_writeICCProfile(self.native()) })
+    }
+
+    /// The transfer function, if it is not a PQ or HLG curve.
+    pub fn is_numerical_transfer_fn(&self) -> Option<ColorSpaceTransferFn> {
+        let mut transfer_fn = named_transfer_fn::LINEAR;
+        unsafe { sb::C_SkColorSpace_isNumericalTransferFn(self.native(), transfer_fn.native_mut()) }
+            .if_true_some(transfer_fn)
+    }
+
+    /// The transfer function that maps encoded values to linear values.
+    pub fn transfer_fn(&self) -> ColorSpaceTransferFn {
+        let mut transfer_fn = named_transfer_fn::LINEAR;
+        unsafe { sb::C_SkColorSpace_transferFn(self.native(), transfer_fn.native_mut()) };
+        transfer_fn
+    }
+
+    /// The inverse of [ColorSpace::transfer_fn()], it maps linear values to encoded values.
+    pub fn inv_transfer_fn(&self) -> ColorSpaceTransferFn {
+        let mut transfer_fn = named_transfer_fn::LINEAR;
+        unsafe { sb::C_SkColorSpace_invTransferFn(self.native(), transfer_fn.native_mut()) };
+        transfer_fn
+    }
+
+    /// The matrix that maps the linear RGB values of this color space to XYZ D50.
+    pub fn to_xyzd50(&self) -> Option<ColorSpaceXYZ> {
+        let mut to_xyzd50 = ColorSpaceXYZ::default();
+        unsafe { sb::C_SkColorSpace_toXYZD50(self.native(), to_xyzd50.native_mut()) }
+            .if_true_some(to_xyzd50)
+    }
+
+    /// The matrix that maps the linear RGB values of this color space to the gamut of `dst`.
+    pub fn gamut_transform_to(&self, dst: &ColorSpace) -> ColorSpaceXYZ {
+        let mut src_to_dst = ColorSpaceXYZ::default();
+        unsafe {
+            sb::C_SkColorSpace_gamutTransformTo(
+                self.native(),
+                dst.native(),
+                src_to_dst.native_mut(),
+            )
+        };
+        src_to_dst
+    }
+
     pub fn to_xyzd50_hash(&self) -> XYZD50Hash {
         XYZD50Hash(self.native().fToXYZD50Hash)
     }
@@ -175,9 +276,6 @@ impl ColorSpace {
         .unwrap()
     }
 
-    // TODO: transferFn()
-    // TODO: invTransferFn()
-    // TODO: gamutTransformTo()
     // TODO: transferFnHash()?
     // TODO: hash()?
 }
@@ -214,3 +312,39 @@ pub fn serialize_and_deserialize() {
 
     assert!(original == deserialized);
 }
+
+#[test]
+#[allow(clippy::float_cmp)]
+pub fn icc_round_trip() {
+    let srgb = ColorSpace::new_srgb();
+    let icc = srgb.to_icc().unwrap();
+    let parsed = ColorSpace::from_icc(icc.as_bytes()).unwrap();
+    assert!(parsed.is_srgb());
+    assert_eq!(parsed.transfer_fn().g, named_transfer_fn::SRGB.g);
+    assert!(ColorSpace::from_icc(&[0u8; 16]).is_none());
+}
+
+#[test]
+pub fn rgb_transfer_fn_and_gamut() {
+    let srgb = ColorSpace::new_srgb();
+    let to_xyzd50 = srgb.to_xyzd50().unwrap();
+    let linear = ColorSpace::new_rgb(&named_transfer_fn::LINEAR, &to_xyzd50).unwrap();
+    assert!(linear == ColorSpace::new_srgb_linear());
+    assert_eq!(
+        linear.is_numerical_transfer_fn(),
+        Some(named_transfer_fn::LINEAR)
+    );
+    let identity = srgb.gamut_transform_to(&linear);
+    for (r, row) in identity.vals.iter().enumerate() {
+        for (c, v) in row.iter().enumerate() {
+            let expected = if r == c { 1.0 } else { 0.0 };
+            assert!((v - expected).abs() < 0.001);
+        }
+    }
+
+    let invalid = ColorSpaceTransferFn {
+        g: f32::NAN,
+        ..named_transfer_fn::LINEAR
+    };
+    assert!(ColorSpace::new_rgb(&invalid, &to_xyzd50).is_none());
+}
@@ -1,8 +1,29 @@
 #[cfg(feature = "gpu")]
 use crate::gpu;
-use crate::{prelude::*, Canvas, Matrix, NativeFlattenable, Point, Rect};
-use skia_bindings::{self as sb, SkDrawable, SkFlattenable, SkRefCntBase};
-use std::fmt;
+use crate::{prelude::*, Canvas, DeserialProcs, Matrix, NativeFlattenable, Picture, Point, Rect};
+use skia_bindings::{
+    self as sb, RustDrawable_Param, SkCanvas, SkDrawable, SkFlattenable, SkRect, SkRefCntBase,
+};
+use std::{ffi, fmt, mem};
+
+/// Content that is drawn by Rust code every time the [Drawable] is drawn.
+///
+/// Unlike pictures, drawables are not snapshots. When a drawable is recorded with
+/// [Canvas::draw_drawable()] into a picture that is finished with
+/// [crate::PictureRecorder::finish_recording_as_drawable()], it is drawn again every time
+/// the resulting drawable is drawn.
+pub trait DrawableImpl {
+    /// Draws the content.
+    fn on_draw(&mut self, canvas: &mut Canvas);
+
+    /// Returns the conservative bounds of what [DrawableImpl::on_draw()] draws.
+    fn on_get_bounds(&mut self) -> Rect;
+
+    /// Returns the approximate number of bytes used by the drawable, for cache accounting.
+    fn on_approximate_bytes_used(&mut self) -> usize {
+        mem::size_of_val(self)
+    }
+}
 
 pub type Drawable = RCHandle<SkDrawable>;
 
@@ -31,6 +52,28 @@ impl fmt::Debug for Drawable {
 }
 
 impl Drawable {
+    /// Creates a drawable that forwards to a Rust implementation.
+    ///
+    /// Call [Drawable::notify_drawing_changed()] when the content `drawable` draws changes.
+    pub fn new<T: DrawableImpl + Send + 'static>(drawable: T) -> Drawable {
+        let param = RustDrawable_Param {
+            data: Box::into_raw(Box::new(drawable)) as *mut ffi::c_void,
+            drop: Some(drop_drawable::<T>),
+            draw: Some(draw::<T>),
+            getBounds: Some(get_bounds::<T>),
+            approximateBytesUsed: Some(approximate_bytes_used::<T>),
+        };
+        Drawable::from_ptr(unsafe { sb::C_RustDrawable_New(&param) }).unwrap()
+    }
+
+    /// Deserializes a drawable, the pictures, images, and typefaces it refers to are
+    /// deserialized by `procs`.
+    pub fn deserialize_with_procs(data: &[u8], procs: &mut DeserialProcs) -> Option<Drawable> {
+        Drawable::from_ptr(unsafe {
+            sb::C_SkDrawable_DeserializeWithProcs(data.as_ptr() as _, data.len(), &procs.native())
+        })
+    }
+
     pub fn draw(&mut self, canvas: &mut Canvas, matrix: Option<&Matrix>) {
         unsafe {
             self.native_mut()
@@ -65,12 +108,10 @@ impl Drawable {
         })
     }
 
-    // TODO: clarify ref-counter situation here, return value is SkPicture*
-    /*
+    /// Records the current content of the drawable into a [Picture].
     pub fn new_picture_snapshot(&mut self) -> Option<Picture> {
-        unimplemented!()
+        Picture::from_ptr(unsafe { sb::C_SkDrawable_newPictureSnapshot(self.native_mut()) })
     }
-    */
 
     pub fn generation_id(&mut self) -> u32 {
         unsafe { self.native_mut().getGenerationID() }
@@ -80,11 +121,104 @@ impl Drawable {
         Rect::from_native_c(unsafe { sb::C_SkDrawable_getBounds(self.native_mut()) })
     }
 
+    pub fn approximate_bytes_used(&mut self) -> usize {
+        unsafe { sb::C_SkDrawable_approximateBytesUsed(self.native_mut()) }
+    }
+
+    /// Invalidates the generation id, so that pictures that contain snapshots of this drawable
+    /// can be recognized as outdated.
     pub fn notify_drawing_changed(&mut self) {
         unsafe { self.native_mut().notifyDrawingChanged() }
     }
 }
 
+unsafe extern "C" fn drop_drawable<T: DrawableImpl>(data: *mut ffi::c_void) {
+    drop(Box::from_raw(data as *mut T))
+}
+
+unsafe extern "C" fn draw<T: DrawableImpl>(data: *mut ffi::c_void, canvas: *mut SkCanvas) {
+    let drawable = &mut *(data as *mut T);
+    drawable.on_draw(Canvas::borrow_from_native_mut(&mut *canvas))
+}
+
+unsafe extern "C" fn get_bounds<T: DrawableImpl>(data: *mut ffi::c_void, bounds: *mut SkRect) {
+    let drawable = &mut *(data as *mut T);
+    *bounds = drawable.on_get_bounds().into_native();
+}
+
+unsafe extern "C" fn approximate_bytes_used<T: DrawableImpl>(data: *mut ffi::c_void) -> usize {
+    let drawable = &mut *(data as *mut T);
+    drawable.on_approximate_bytes_used()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Drawable, DrawableImpl};
+    use crate::{Canvas, Color, Paint, PictureRecorder, Rect, Surface};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    struct Square {
+        blue: Arc<AtomicBool>,
+    }
+
+    impl DrawableImpl for Square {
+        fn on_draw(&mut self, canvas: &mut Canvas) {
+            let mut paint = Paint::default();
+            paint.set_color(if self.blue.load(Ordering::SeqCst) {
+                Color::BLUE
+            } else {
+                Color::RED
+            });
+            canvas.draw_rect(self.on_get_bounds(), &paint);
+        }
+
+        fn on_get_bounds(&mut self) -> Rect {
+            Rect::from_wh(10.0, 10.0)
+        }
+    }
+
+    fn pixel(surface: &mut Surface) -> Color {
+        let mut bitmap = crate::Bitmap::new();
+        bitmap.alloc_n32_pixels((10, 10), None);
+        assert!(surface.read_pixels_to_bitmap(&bitmap, (0, 0)));
+        bitmap.get_color((5, 5))
+    }
+
+    #[test]
+    fn rust_drawable_is_redrawn_from_a_recorded_drawable() {
+        let blue = Arc::new(AtomicBool::new(false));
+        let mut drawable = Drawable::new(Square { blue: blue.clone() });
+        assert_eq!(drawable.bounds(), Rect::from_wh(10.0, 10.0));
+        assert!(drawable.approximate_bytes_used() > 0);
+
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::from_wh(10.0, 10.0), None);
+        canvas.draw_drawable(&mut drawable, None);
+        let mut recorded = recorder.finish_recording_as_drawable().unwrap();
+
+        let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+        recorded.draw(surface.canvas(), None);
+        assert_eq!(pixel(&mut surface), Color::RED);
+
+        let generation_id = drawable.generation_id();
+        blue.store(true, Ordering::SeqCst);
+        drawable.notify_drawing_changed();
+        assert_ne!(drawable.generation_id(), generation_id);
+        recorded.draw(surface.canvas(), None);
+        assert_eq!(pixel(&mut surface), Color::BLUE);
+
+        let snapshot = drawable.new_picture_snapshot().unwrap();
+        assert_eq!(snapshot.cull_rect(), Rect::from_wh(10.0, 10.0));
+        drop(drawable);
+        assert_eq!(Arc::strong_count(&blue), 2);
+        drop(recorded);
+        assert_eq!(Arc::strong_count(&blue), 1);
+    }
+}
+
 #[cfg(feature = "gpu")]
 pub use gpu_draw_handler::*;
 
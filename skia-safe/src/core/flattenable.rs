@@ -1,15 +1,63 @@
 use crate::prelude::*;
-use crate::Data;
+use crate::{
+    ColorFilter, Data, Drawable, ImageFilter, MaskFilter, PathEffect, SerialProcs, Shader,
+};
 use skia_bindings as sb;
-use skia_bindings::SkFlattenable;
-use std::ffi::CStr;
+use skia_bindings::{
+    SkColorFilter, SkDrawable, SkFlattenable, SkFlattenable_Type, SkImageFilter, SkMaskFilter,
+    SkPathEffect, SkShader,
+};
+use std::ffi::{CStr, CString};
 
-// TODO: getFactory()?
-// TODO: NameToFactory()?
-// TODO: FactoryToName()?
-// TODO: Register()?
-// TODO: getFlattenableType()?
-// TODO: serialize() with SkSerialProcs?
+// TODO: getFactory()? Factories are native function pointers that can not be called from Rust,
+//       they are referred to by their registered names instead.
+// Register() is not exposed: Skia's registry is a fixed size array that is sorted once and then
+// read without locking, so registering factories later is not supported.
+
+/// The base type of a flattenable, which decides what kind of object is created when it is
+/// deserialized.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FlattenableType {
+    ColorFilter = SkFlattenable_Type::SkColorFilter as _,
+    Drawable = SkFlattenable_Type::SkDrawable as _,
+    DrawLooper = SkFlattenable_Type::SkDrawLooper as _,
+    ImageFilter = SkFlattenable_Type::SkImageFilter as _,
+    MaskFilter = SkFlattenable_Type::SkMaskFilter as _,
+    PathEffect = SkFlattenable_Type::SkPathEffect as _,
+    PixelRef = SkFlattenable_Type::SkPixelRef as _,
+    Shader = SkFlattenable_Type::SkShaderBase as _,
+}
+
+impl FlattenableType {
+    pub(crate) fn from_native(native: SkFlattenable_Type) -> Option<Self> {
+        use SkFlattenable_Type::*;
+        Some(match native {
+            SkColorFilter => FlattenableType::ColorFilter,
+            SkDrawable => FlattenableType::Drawable,
+            SkDrawLooper => FlattenableType::DrawLooper,
+            SkImageFilter => FlattenableType::ImageFilter,
+            SkMaskFilter => FlattenableType::MaskFilter,
+            SkPathEffect => FlattenableType::PathEffect,
+            SkPixelRef => FlattenableType::PixelRef,
+            SkShaderBase => FlattenableType::Shader,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn into_native(self) -> SkFlattenable_Type {
+        use SkFlattenable_Type::*;
+        match self {
+            FlattenableType::ColorFilter => SkColorFilter,
+            FlattenableType::Drawable => SkDrawable,
+            FlattenableType::DrawLooper => SkDrawLooper,
+            FlattenableType::ImageFilter => SkImageFilter,
+            FlattenableType::MaskFilter => SkMaskFilter,
+            FlattenableType::PathEffect => SkPathEffect,
+            FlattenableType::PixelRef => SkPixelRef,
+            FlattenableType::Shader => SkShaderBase,
+        }
+    }
+}
 
 pub trait Flattenable: Sized {
     fn type_name(&self) -> &CStr;
@@ -44,3 +92,145 @@ where
         RCHandle::from_ptr(N::native_deserialize(data))
     }
 }
+
+impl<N> RCHandle<N>
+where
+    N: NativeFlattenable + NativeRefCountedBase,
+{
+    pub fn flattenable_type(&self) -> FlattenableType {
+        FlattenableType::from_native(unsafe {
+            sb::C_SkFlattenable_getFlattenableType(self.native().native_flattenable())
+        })
+        .unwrap()
+    }
+
+    /// The name the factory that deserializes the object is registered with, or `None` if the
+    /// object can not be deserialized.
+    pub fn factory_name(&self) -> Option<&CStr> {
+        let name =
+            unsafe { sb::C_SkFlattenable_getFactoryName(self.native().native_flattenable()) };
+        (!name.is_null()).if_true_then_some(|| unsafe { CStr::from_ptr(name) })
+    }
+
+    /// Serializes the object, the images and typefaces it refers to are serialized by `procs`.
+    pub fn serialize_with_procs(&self, procs: &mut SerialProcs) -> Data {
+        Data::from_ptr(unsafe {
+            sb::C_SkFlattenable_serializeWithProcs(
+                self.native().native_flattenable(),
+                &procs.native(),
+            )
+        })
+        .unwrap()
+    }
+}
+
+/// Returns `true` if a flattenable factory is registered with `name`.
+pub fn is_registered(name: impl AsRef<str>) -> bool {
+    CString::new(name.as_ref())
+        .map(|name| unsafe { sb::C_SkFlattenable_IsRegistered(name.as_ptr()) })
+        .unwrap_or(false)
+}
+
+/// A flattenable of any of the types that can be deserialized into a Rust handle.
+#[derive(Clone, Debug)]
+pub enum AnyFlattenable {
+    ColorFilter(ColorFilter),
+    Drawable(Drawable),
+    ImageFilter(ImageFilter),
+    MaskFilter(MaskFilter),
+    PathEffect(PathEffect),
+    Shader(Shader),
+}
+
+impl AnyFlattenable {
+    /// Deserializes a flattenable of the base type `ty` from `data`.
+    ///
+    /// Returns `None` if `data` is invalid, or if there is no Rust handle for flattenables of
+    /// the type `ty`.
+    pub fn deserialize(ty: FlattenableType, data: &[u8]) -> Option<AnyFlattenable> {
+        let ptr = match ty {
+            FlattenableType::DrawLooper | FlattenableType::PixelRef => return None,
+            ty => unsafe {
+                sb::C_SkFlattenable_Deserialize(ty.into_native(), data.as_ptr() as _, data.len())
+            },
+        };
+        Some(match ty {
+            FlattenableType::ColorFilter => {
+                AnyFlattenable::ColorFilter(ColorFilter::from_ptr(ptr as *mut SkColorFilter)?)
+            }
+            FlattenableType::Drawable => {
+                AnyFlattenable::Drawable(Drawable::from_ptr(ptr as *mut SkDrawable)?)
+            }
+            FlattenableType::ImageFilter => {
+                AnyFlattenable::ImageFilter(ImageFilter::from_ptr(ptr as *mut SkImageFilter)?)
+            }
+            FlattenableType::MaskFilter => {
+                AnyFlattenable::MaskFilter(MaskFilter::from_ptr(ptr as *mut SkMaskFilter)?)
+            }
+            FlattenableType::PathEffect => {
+                AnyFlattenable::PathEffect(PathEffect::from_ptr(ptr as *mut SkPathEffect)?)
+            }
+            FlattenableType::Shader => {
+                AnyFlattenable::Shader(Shader::from_ptr(ptr as *mut SkShader)?)
+            }
+            FlattenableType::DrawLooper | FlattenableType::PixelRef => unreachable!(),
+        })
+    }
+
+    pub fn flattenable_type(&self) -> FlattenableType {
+        match self {
+            AnyFlattenable::ColorFilter(_) => FlattenableType::ColorFilter,
+            AnyFlattenable::Drawable(_) => FlattenableType::Drawable,
+            AnyFlattenable::ImageFilter(_) => FlattenableType::ImageFilter,
+            AnyFlattenable::MaskFilter(_) => FlattenableType::MaskFilter,
+            AnyFlattenable::PathEffect(_) => FlattenableType::PathEffect,
+            AnyFlattenable::Shader(_) => FlattenableType::Shader,
+        }
+    }
+
+    pub fn type_name(&self) -> &CStr {
+        match self {
+            AnyFlattenable::ColorFilter(f) => f.type_name(),
+            AnyFlattenable::Drawable(f) => f.type_name(),
+            AnyFlattenable::ImageFilter(f) => f.type_name(),
+            AnyFlattenable::MaskFilter(f) => f.type_name(),
+            AnyFlattenable::PathEffect(f) => f.type_name(),
+            AnyFlattenable::Shader(f) => f.type_name(),
+        }
+    }
+
+    pub fn serialize(&self) -> Data {
+        match self {
+            AnyFlattenable::ColorFilter(f) => f.serialize(),
+            AnyFlattenable::Drawable(f) => f.serialize(),
+            AnyFlattenable::ImageFilter(f) => f.serialize(),
+            AnyFlattenable::MaskFilter(f) => f.serialize(),
+            AnyFlattenable::PathEffect(f) => f.serialize(),
+            AnyFlattenable::Shader(f) => f.serialize(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_registered, AnyFlattenable, FlattenableType};
+    use crate::{color_filters, BlendMode, Color, Flattenable, PathEffect};
+
+    #[test]
+    fn type_and_factory_name() {
+        let filter = color_filters::blend(Color::RED, BlendMode::SrcOver).unwrap();
+        assert_eq!(filter.flattenable_type(), FlattenableType::ColorFilter);
+        let name = filter.factory_name().unwrap().to_str().unwrap();
+        assert!(is_registered(name));
+    }
+
+    #[test]
+    fn deserialize_by_type() {
+        let effect = PathEffect::discrete(10.0, 4.0, None).unwrap();
+        let data = effect.serialize();
+        let any = AnyFlattenable::deserialize(FlattenableType::PathEffect, &data).unwrap();
+        assert_eq!(any.flattenable_type(), FlattenableType::PathEffect);
+        assert_eq!(any.type_name(), effect.type_name());
+        assert!(AnyFlattenable::deserialize(FlattenableType::Shader, &data).is_none());
+    }
+}
@@ -1,11 +1,17 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::Data;
 use crate::{
-    interop::{self, DynamicMemoryWStream},
+    interop::{self, DynamicMemoryWStream, StreamAsset},
     prelude::*,
-    FontStyle, Typeface, Unichar,
+    FontArguments, FontStyle, Typeface, Unichar,
 };
 use core::fmt;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use skia_bindings::SkData;
 use skia_bindings::{self as sb, SkFontMgr, SkFontStyleSet, SkRefCntBase};
-use std::{ffi::CString, mem, os::raw::c_char};
+use std::{ffi::CString, io, mem, os::raw::c_char};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::{os::unix::ffi::OsStrExt, path::Path};
 
 pub type FontStyleSet = RCHandle<SkFontStyleSet>;
 
@@ -110,6 +116,36 @@ impl FontMgr {
         FontMgr::from_ptr(unsafe { sb::C_SkFontMgr_RefDefault() }).unwrap()
     }
 
+    /// Creates a font manager that provides the fonts found in the directory `path` and its
+    /// subdirectories, independent of the fonts installed on the system.
+    ///
+    /// Typefaces are matched by character in all families if the requested family does not
+    /// contain the character.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn new_from_directory(path: impl AsRef<Path>) -> Self {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes()).unwrap();
+        FontMgr::from_ptr(unsafe { sb::C_SkFontMgr_NewCustomDirectory(path.as_ptr()) }).unwrap()
+    }
+
+    /// Creates a font manager that provides the fonts in `fonts`, the contents of font files,
+    /// like [FontMgr::new_from_directory()].
+    ///
+    /// The typefaces keep a reference to their font data.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn new_from_typefaces(fonts: impl IntoIterator<Item = Data>) -> Self {
+        let fonts: Vec<Data> = fonts.into_iter().collect();
+        let datas: Vec<*const SkData> = fonts.iter().map(|font| font.native() as _).collect();
+        FontMgr::from_ptr(unsafe { sb::C_SkFontMgr_NewFromFontData(datas.as_ptr(), datas.len()) })
+            .unwrap()
+    }
+
+    /// Creates a font manager that does not provide any fonts, but can create typefaces from
+    /// font data, see [FontMgr::new_from_data()].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn new_custom_empty() -> Self {
+        FontMgr::from_ptr(unsafe { sb::C_SkFontMgr_NewCustomEmpty() }).unwrap()
+    }
+
     pub fn count_families(&self) -> usize {
         unsafe { self.native().countFamilies().try_into().unwrap() }
     }
@@ -200,12 +236,85 @@ impl FontMgr {
         })
     }
 
-    // TODO: makeFromStream(.., ttcIndex).
+    /// Creates a typeface from the font `ttc_index` read from `stream`, without reading the
+    /// whole font into memory.
+    pub fn new_from_stream<R: io::Read + io::Seek + Send + 'static>(
+        &self,
+        stream: R,
+        ttc_index: impl Into<Option<usize>>,
+    ) -> Option<Typeface> {
+        self.new_from_stream_asset(
+            StreamAsset::from_reader(stream),
+            ttc_index.into().unwrap_or_default(),
+        )
+    }
+
+    /// Creates a typeface from the font read from `stream`, with the collection index and the
+    /// variation design position of `arguments`.
+    pub fn new_from_stream_with_arguments<R: io::Read + io::Seek + Send + 'static>(
+        &self,
+        stream: R,
+        arguments: &FontArguments,
+    ) -> Option<Typeface> {
+        let stream = StreamAsset::from_reader(stream);
+        Typeface::from_ptr(unsafe {
+            sb::C_SkFontMgr_makeFromStreamWithArguments(
+                self.native(),
+                stream.into_stream_ptr(),
+                arguments.native(),
+            )
+        })
+    }
+
+    /// Creates typefaces from all the fonts of the font collection (`.ttc` / `.otc`) read from
+    /// `stream`, or from the single font read from `stream` if it is not a collection.
+    ///
+    /// The typefaces share `stream`, so the fonts are not read into memory.
+    pub fn new_collection_from_stream<R: io::Read + io::Seek + Send + 'static>(
+        &self,
+        mut stream: R,
+    ) -> Vec<Typeface> {
+        let count = match collection_font_count(&mut stream) {
+            Ok(count) => count,
+            Err(_) => return Vec::new(),
+        };
+        let stream = StreamAsset::from_reader(stream);
+        (0..count)
+            .filter_map(|index| self.new_from_stream_asset(stream.duplicate()?, index))
+            .collect()
+    }
+
+    fn new_from_stream_asset(&self, stream: StreamAsset, ttc_index: usize) -> Option<Typeface> {
+        let ttc_index = ttc_index.try_into().ok()?;
+        Typeface::from_ptr(unsafe {
+            sb::C_SkFontMgr_makeFromStream(self.native(), stream.into_stream_ptr(), ttc_index)
+        })
+    }
+}
+
+/// Returns the number of fonts in the font collection read from `stream`, `1` if it is not a
+/// collection.
+fn collection_font_count(stream: &mut (impl io::Read + io::Seek)) -> io::Result<usize> {
+    let length = stream.seek(io::SeekFrom::End(0))?;
+    stream.seek(io::SeekFrom::Start(0))?;
+    let mut header = [0u8; 12];
+    if length < header.len() as u64 {
+        return Ok(1);
+    }
+    stream.read_exact(&mut header)?;
+    if &header[0..4] != b"ttcf" {
+        return Ok(1);
+    }
+    let count = u32::from_be_bytes([header[8], header[9], header[10], header[11]]) as u64;
+    // The header is followed by the offsets of all fonts.
+    Ok(count.min((length - header.len() as u64) / 4) as usize)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::FontMgr;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    use crate::{Data, FontStyle};
+    use crate::{FontArguments, FontMgr, Typeface};
 
     #[test]
     #[serial_test::serial]
@@ -230,4 +339,111 @@ mod tests {
             }
         }
     }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn default_font_data() -> (Vec<u8>, String) {
+        let typeface = Typeface::default();
+        let (data, _) = typeface.to_font_data().unwrap();
+        (data, typeface.family_name())
+    }
+
+    #[test]
+    #[serial_test::serial]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn font_mgr_from_directory() {
+        let (data, family_name) = default_font_data();
+        struct TempDir(std::path::PathBuf);
+        impl Drop for TempDir {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_dir_all(&self.0);
+            }
+        }
+
+        let dir = TempDir(
+            std::env::temp_dir().join(format!("skia-safe-font-mgr-{}", std::process::id())),
+        );
+        let dir = &dir.0;
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("font.ttf"), &data).unwrap();
+
+        let font_mgr = FontMgr::new_from_directory(dir);
+        assert!(font_mgr.family_names().any(|name| name == family_name));
+        let typeface = font_mgr
+            .match_family_style(&family_name, FontStyle::normal())
+            .unwrap();
+        assert_eq!(typeface.family_name(), family_name);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn font_mgr_from_typefaces_falls_back_by_character() {
+        let (data, family_name) = default_font_data();
+
+        let font_mgr = FontMgr::new_from_typefaces(Some(Data::new_copy(&data)));
+        assert_eq!(font_mgr.count_families(), 1);
+        let typeface = font_mgr
+            .match_family_style_character("unknown family", FontStyle::normal(), &[], 'A' as _)
+            .unwrap();
+        drop(font_mgr);
+        assert_eq!(typeface.family_name(), family_name);
+        assert_ne!(typeface.unichar_to_glyph('A' as _), 0);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn empty_custom_font_mgr_creates_typefaces_from_data() {
+        let (data, family_name) = default_font_data();
+        let font_mgr = FontMgr::new_custom_empty();
+        let typeface = font_mgr.new_from_data(&data, None).unwrap();
+        assert_eq!(typeface.family_name(), family_name);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn typefaces_from_streams() {
+        use super::collection_font_count;
+        use std::io::Cursor;
+
+        let typeface = Typeface::default();
+        let (data, index) = typeface.to_font_data().unwrap();
+        let font_mgr = FontMgr::default();
+
+        let from_stream = font_mgr
+            .new_from_stream(Cursor::new(data.clone()), index)
+            .unwrap();
+        assert_eq!(from_stream.family_name(), typeface.family_name());
+        assert_eq!(from_stream.count_glyphs(), typeface.count_glyphs());
+        assert!(font_mgr
+            .new_from_stream(Cursor::new(data.clone()), usize::MAX)
+            .is_none());
+
+        let mut arguments = FontArguments::new();
+        arguments.set_collection_index(index);
+        assert!(font_mgr
+            .new_from_stream_with_arguments(Cursor::new(data.clone()), &arguments)
+            .is_some());
+
+        let count = collection_font_count(&mut Cursor::new(&data)).unwrap();
+        let typefaces = font_mgr.new_collection_from_stream(Cursor::new(data));
+        assert_eq!(typefaces.len(), count);
+        assert!(typefaces
+            .iter()
+            .any(|face| face.family_name() == typeface.family_name()));
+    }
+
+    #[test]
+    fn collection_font_count_reads_the_ttc_header() {
+        use super::collection_font_count;
+        use std::io::Cursor;
+
+        let mut ttc = b"ttcf\x00\x01\x00\x00\x00\x00\x00\x02".to_vec();
+        ttc.extend_from_slice(&[0; 8]);
+        assert_eq!(collection_font_count(&mut Cursor::new(ttc)).unwrap(), 2);
+        assert_eq!(
+            collection_font_count(&mut Cursor::new(b"\x00\x01\x00\x00".to_vec())).unwrap(),
+            1
+        );
+    }
 }
@@ -1,4 +1,5 @@
-use skia_bindings::SkGraphics;
+use crate::{native_trace_memory_dump, TraceMemoryDump};
+use skia_bindings::{self as sb, SkGraphics};
 use std::ffi::CString;
 
 pub fn init() {
@@ -69,7 +70,12 @@ pub fn set_resource_cache_single_allocation_byte_limit(new_limit: Option<usize>)
     }
 }
 
-// TODO: dump_memory_statistics() (needs SkTraceMemoryDumpWrapper interop wrapper).
+/// Reports the memory statistics of the font cache, the resource cache, and other global caches
+/// to `dump`.
+pub fn dump_memory_statistics(dump: &mut impl TraceMemoryDump) {
+    let param = native_trace_memory_dump(dump);
+    unsafe { sb::C_SkGraphics_DumpMemoryStatistics(&param) }
+}
 
 pub fn purge_all_caches() {
     unsafe { SkGraphics::PurgeAllCaches() }
@@ -3,10 +3,10 @@ use crate::gpu;
 use crate::{
     prelude::*, AlphaType, Bitmap, ColorSpace, ColorType, Data, EncodedImageFormat, IPoint, IRect,
     ISize, ImageFilter, ImageGenerator, ImageInfo, Matrix, Paint, Picture, Pixmap, SamplingOptions,
-    Shader, TileMode,
+    Shader, TileMode, YUVColorSpace,
 };
-use skia_bindings::{self as sb, SkImage, SkRefCntBase};
-use std::{fmt, mem, ptr};
+use skia_bindings::{self as sb, SkImage, SkImage_AsyncReadResult, SkRefCntBase};
+use std::{ffi, fmt, mem, ptr, slice};
 
 pub use super::CubicResampler;
 
@@ -14,6 +14,110 @@ pub use skia_bindings::SkImage_BitDepth as BitDepth;
 pub use skia_bindings::SkImage_CachingHint as CachingHint;
 pub use skia_bindings::SkImage_CompressionType as CompressionType;
 
+pub use skia_bindings::SkImage_RescaleGamma as RescaleGamma;
+#[test]
+fn test_image_rescale_gamma_naming() {
+    let _ = RescaleGamma::Linear;
+}
+
+pub use skia_bindings::SkImage_RescaleMode as RescaleMode;
+#[test]
+fn test_image_rescale_mode_naming() {
+    let _ = RescaleMode::RepeatedCubic;
+}
+
+/// The pixels read by [Image::async_rescale_and_read_pixels()],
+/// [Image::async_rescale_and_read_pixels_yuv420()], and their [crate::Surface] variants.
+///
+/// Contains one plane for RGBA reads, and the Y, U, and V planes for YUV420 reads.
+pub struct AsyncReadResult {
+    native: RefHandle<SkImage_AsyncReadResult>,
+    plane_heights: Vec<usize>,
+}
+
+// The result owns its pixels and is only read after it was created, on the GPU backend, it is
+// created on the thread that finishes the GPU work.
+unsafe impl Send for AsyncReadResult {}
+unsafe impl Sync for AsyncReadResult {}
+
+impl NativeDrop for SkImage_AsyncReadResult {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkImage_AsyncReadResult_delete(self) }
+    }
+}
+
+impl fmt::Debug for AsyncReadResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncReadResult")
+            .field("count", &self.count())
+            .field("plane_heights", &self.plane_heights)
+            .finish()
+    }
+}
+
+impl AsyncReadResult {
+    /// The number of planes.
+    pub fn count(&self) -> usize {
+        unsafe { sb::C_SkImage_AsyncReadResult_count(self.native.native()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// The pixels of plane `i`.
+    pub fn data(&self, i: usize) -> &[u8] {
+        assert!(i < self.count());
+        let len = self.row_bytes(i) * self.plane_heights[i];
+        unsafe {
+            let data =
+                sb::C_SkImage_AsyncReadResult_data(self.native.native(), i.try_into().unwrap());
+            slice::from_raw_parts(data as *const u8, len)
+        }
+    }
+
+    /// The row bytes of plane `i`.
+    pub fn row_bytes(&self, i: usize) -> usize {
+        assert!(i < self.count());
+        unsafe {
+            sb::C_SkImage_AsyncReadResult_rowBytes(self.native.native(), i.try_into().unwrap())
+        }
+    }
+}
+
+/// Returns the callback and context that pass the result of an asynchronous pixel read to
+/// `callback`.
+pub(crate) fn read_pixels_callback<F>(
+    plane_heights: Vec<usize>,
+    callback: F,
+) -> (sb::RustReadPixelsCallback, *mut ffi::c_void)
+where
+    F: FnOnce(Option<AsyncReadResult>) + Send + 'static,
+{
+    (
+        Some(read_pixels_trampoline::<F>),
+        Box::into_raw(Box::new((plane_heights, callback))) as _,
+    )
+}
+
+unsafe extern "C" fn read_pixels_trampoline<F: FnOnce(Option<AsyncReadResult>)>(
+    context: *mut ffi::c_void,
+    result: *const SkImage_AsyncReadResult,
+) {
+    let (plane_heights, callback) = *Box::from_raw(context as *mut (Vec<usize>, F));
+    callback(
+        RefHandle::from_ptr(result as *mut _).map(|native| AsyncReadResult {
+            native,
+            plane_heights,
+        }),
+    )
+}
+
+/// The heights of the Y, U, and V planes of a YUV420 read of `height`.
+pub(crate) fn yuv420_plane_heights(height: i32) -> Vec<usize> {
+    let height: usize = height.try_into().unwrap();
+    let uv_height = (height + 1) / 2;
+    vec![height, uv_height, uv_height]
+}
+
 pub type Image = RCHandle<SkImage>;
 unsafe impl Send for Image {}
 unsafe impl Sync for Image {}
@@ -498,9 +602,74 @@ impl Image {
             .readPixels1(ptr::null_mut(), dst.native(), src.x, src.y, caching_hint)
     }
 
-    // TODO:
-    // AsyncReadResult, ReadPixelsContext, ReadPixelsCallback, RescaleGamma,
-    // asyncRescaleAndReadPixels, asyncRescaleAndReadPixelsYUV420
+    /// Reads the pixels of `src_rect`, rescaled to the dimensions of `info` and converted to its
+    /// color type, alpha type, and color space, and passes them to `callback`.
+    ///
+    /// `callback` receives `None` if the read fails. On the raster backend, `callback` is called
+    /// before this function returns, on the GPU backend, it is called after the GPU work was
+    /// submitted and finished, possibly on another thread.
+    ///
+    /// `rescale_gamma` decides if the rescaling is done in a linear or the source's gamma space,
+    /// `rescale_mode` decides how pixels are interpolated.
+    pub fn async_rescale_and_read_pixels(
+        &self,
+        info: &ImageInfo,
+        src_rect: impl AsRef<IRect>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + Send + 'static,
+    ) {
+        let (callback, context) =
+            read_pixels_callback(vec![info.height().try_into().unwrap()], callback);
+        unsafe {
+            sb::C_SkImage_asyncRescaleAndReadPixels(
+                self.native(),
+                info.native(),
+                src_rect.as_ref().native(),
+                rescale_gamma,
+                rescale_mode,
+                callback,
+                context,
+            )
+        }
+    }
+
+    /// Reads the pixels of `src_rect`, rescaled to `dst_size` and converted to the Y, U, and V
+    /// planes of `yuv_color_space` in `dst_color_space`, and passes them to `callback`.
+    ///
+    /// The U and V planes are subsampled by a factor of 2 in both directions. The dimensions of
+    /// `dst_size` must be even. See [Self::async_rescale_and_read_pixels()].
+    ///
+    /// YUV420 reads are only supported on the GPU backend, on the raster backend, `callback`
+    /// always receives `None`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn async_rescale_and_read_pixels_yuv420(
+        &self,
+        yuv_color_space: YUVColorSpace,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+        src_rect: impl AsRef<IRect>,
+        dst_size: impl Into<ISize>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + Send + 'static,
+    ) {
+        let dst_size = dst_size.into();
+        let (callback, context) =
+            read_pixels_callback(yuv420_plane_heights(dst_size.height), callback);
+        unsafe {
+            sb::C_SkImage_asyncRescaleAndReadPixelsYUV420(
+                self.native(),
+                yuv_color_space,
+                dst_color_space.into().into_ptr_or_null(),
+                src_rect.as_ref().native(),
+                dst_size.native(),
+                rescale_gamma,
+                rescale_mode,
+                callback,
+                context,
+            )
+        }
+    }
 
     #[must_use]
     pub fn scale_pixels(
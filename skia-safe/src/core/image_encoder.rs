@@ -1,22 +1,68 @@
-use crate::{Bitmap, Data, EncodedImageFormat, Pixmap};
+use crate::interop::{DynamicMemoryWStream, RustWStream};
+use crate::prelude::*;
+use crate::{Bitmap, ColorSpace, Data, EncodedImageFormat, Pixmap};
+use skia_bindings::SkWStream;
+use std::io;
 
 impl Pixmap {
     pub fn encode(&self, format: EncodedImageFormat, quality: usize) -> Option<Data> {
         crate::encode::pixmap(self, format, quality)
     }
+
+    /// Encodes the pixmap as if it were tagged with `color_space`, see
+    /// [crate::encode::pixmap_with_color_space()].
+    pub fn encode_with_color_space(
+        &self,
+        format: EncodedImageFormat,
+        quality: usize,
+        color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<Data> {
+        crate::encode::pixmap_with_color_space(self, format, quality, color_space)
+    }
 }
 
 impl Bitmap {
     pub fn encode(&self, format: EncodedImageFormat, quality: usize) -> Option<Data> {
         crate::encode::bitmap(self, format, quality)
     }
+
+    /// Encodes the bitmap as if it were tagged with `color_space`, see
+    /// [crate::encode::pixmap_with_color_space()].
+    pub fn encode_with_color_space(
+        &self,
+        format: EncodedImageFormat,
+        quality: usize,
+        color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<Data> {
+        crate::encode::pixmap_with_color_space(self.pixmap(), format, quality, color_space)
+    }
 }
 
-pub mod encode {
-    // TODO: wrap stream variants.
+/// Runs `encode` on a memory stream and returns the encoded bytes, or `None` if it failed.
+pub(crate) fn encode_to_data(encode: impl FnOnce(&mut SkWStream) -> bool) -> Option<Data> {
+    let mut stream = DynamicMemoryWStream::new();
+    encode(stream.native_mut().base_mut()).if_true_then_some(|| stream.detach_as_data())
+}
+
+/// Runs `encode` on a stream that writes to `writer`.
+///
+/// Returns the first error of `writer` if writing failed.
+pub(crate) fn encode_to_writer(
+    writer: &mut impl io::Write,
+    encode: impl FnOnce(&mut SkWStream) -> bool,
+) -> io::Result<()> {
+    let mut stream = RustWStream::new(writer);
+    if encode(stream.stream_mut()) {
+        return Ok(());
+    }
+    Err(stream
+        .take_error()
+        .unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "failed to encode the image")))
+}
 
+pub mod encode {
     use crate::prelude::*;
-    use crate::{Bitmap, Data, EncodedImageFormat, Pixmap};
+    use crate::{Bitmap, ColorSpace, Data, EncodedImageFormat, Pixmap};
     use skia_bindings as sb;
 
     pub fn pixmap(src: &Pixmap, format: EncodedImageFormat, quality: usize) -> Option<Data> {
@@ -30,4 +76,25 @@ pub mod encode {
             sb::C_SkEncodeBitmap(src.native(), format, quality.try_into().unwrap())
         })
     }
+
+    /// Encodes the pixels of `src` as if they were tagged with `color_space` instead of the
+    /// pixmap's color space.
+    ///
+    /// The PNG, JPEG and WebP encoders embed an ICC profile that describes `color_space`, `None`
+    /// encodes the image without a profile.
+    pub fn pixmap_with_color_space(
+        src: &Pixmap,
+        format: EncodedImageFormat,
+        quality: usize,
+        color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<Data> {
+        Data::from_ptr(unsafe {
+            sb::C_SkEncodePixmapWithColorSpace(
+                src.native(),
+                color_space.into().into_ptr_or_null(),
+                format,
+                quality.try_into().unwrap(),
+            )
+        })
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{prelude::*, ColorFilter, IRect, Matrix, NativeFlattenable, Rect};
+use crate::{prelude::*, ColorFilter, DeserialProcs, IRect, Matrix, NativeFlattenable, Rect};
 use skia_bindings::{self as sb, SkColorFilter, SkFlattenable, SkImageFilter, SkRefCntBase};
 use std::{fmt, ptr};
 
@@ -42,6 +42,17 @@ impl fmt::Debug for ImageFilter {
 }
 
 impl ImageFilter {
+    /// Deserializes an image filter, the images it refers to are deserialized by `procs`.
+    pub fn deserialize_with_procs(data: &[u8], procs: &mut DeserialProcs) -> Option<ImageFilter> {
+        ImageFilter::from_ptr(unsafe {
+            sb::C_SkImageFilter_DeserializeWithProcs(
+                data.as_ptr() as _,
+                data.len(),
+                &procs.native(),
+            )
+        })
+    }
+
     // TODO: wrapfilterImage()? SkSpecialImage is declared in src/core/
 
     pub fn filter_bounds<'a>(
@@ -1,8 +1,29 @@
 #[cfg(feature = "gpu")]
 use crate::gpu;
 use crate::{image, prelude::*, ColorSpace, Data, ISize, ImageInfo, Matrix, Paint, Picture};
-use skia_bindings::{self as sb, SkImageGenerator};
-use std::fmt;
+use skia_bindings::{self as sb, RustImageGenerator_Param, SkData, SkImageGenerator, SkImageInfo};
+use std::{ffi, fmt, slice};
+
+/// Produces the pixels of an [ImageGenerator] in Rust code, on demand.
+///
+/// An [crate::Image] created with [crate::Image::from_generator()] from a generator returned by
+/// [ImageGenerator::new()] stays lazy, [ImageGeneratorImpl::on_get_pixels()] is called when Skia
+/// needs the pixels, for example, when the image is drawn.
+pub trait ImageGeneratorImpl {
+    /// The info of the image generated, it is queried once when the generator is created.
+    fn info(&self) -> ImageInfo;
+
+    /// Generates the pixels of the image into `pixels` with `row_bytes`.
+    ///
+    /// `info` may differ from [ImageGeneratorImpl::info()] in its color type, alpha type, and
+    /// color space. Returns `false` if the pixels can not be generated in `info`.
+    fn on_get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool;
+
+    /// Returns the encoded data of the image, if the pixels are decoded from it.
+    fn on_ref_encoded_data(&mut self) -> Option<Data> {
+        None
+    }
+}
 
 pub type ImageGenerator = RefHandle<SkImageGenerator>;
 unsafe impl Send for ImageGenerator {}
@@ -24,6 +45,19 @@ impl fmt::Debug for ImageGenerator {
 }
 
 impl ImageGenerator {
+    /// Creates a generator that forwards to a Rust implementation.
+    pub fn new<T: ImageGeneratorImpl + Send + 'static>(generator: T) -> ImageGenerator {
+        let info = generator.info();
+        let param = RustImageGenerator_Param {
+            data: Box::into_raw(Box::new(generator)) as *mut ffi::c_void,
+            drop: Some(drop_generator::<T>),
+            getPixels: Some(get_pixels::<T>),
+            refEncodedData: Some(ref_encoded_data::<T>),
+        };
+        ImageGenerator::from_ptr(unsafe { sb::C_RustImageGenerator_New(info.native(), &param) })
+            .unwrap()
+    }
+
     pub fn unique_id(&self) -> u32 {
         self.native().fUniqueID
     }
@@ -84,3 +118,111 @@ impl ImageGenerator {
         })
     }
 }
+
+unsafe extern "C" fn drop_generator<T: ImageGeneratorImpl>(data: *mut ffi::c_void) {
+    drop(Box::from_raw(data as *mut T))
+}
+
+unsafe extern "C" fn get_pixels<T: ImageGeneratorImpl>(
+    data: *mut ffi::c_void,
+    info: *const SkImageInfo,
+    pixels: *mut ffi::c_void,
+    row_bytes: usize,
+) -> bool {
+    let generator = &mut *(data as *mut T);
+    let info = ImageInfo::from_native_ref(&*info);
+    let len = info.compute_byte_size(row_bytes);
+    if pixels.is_null() || len == usize::MAX {
+        return false;
+    }
+    let pixels = slice::from_raw_parts_mut(pixels as *mut u8, len);
+    generator.on_get_pixels(info, pixels, row_bytes)
+}
+
+unsafe extern "C" fn ref_encoded_data<T: ImageGeneratorImpl>(
+    data: *mut ffi::c_void,
+) -> *mut SkData {
+    let generator = &mut *(data as *mut T);
+    generator.on_ref_encoded_data().into_ptr_or_null()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageGenerator, ImageGeneratorImpl};
+    use crate::{AlphaType, Color, ColorType, Data, Image, ImageInfo, Surface};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct Checkerboard {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl ImageGeneratorImpl for Checkerboard {
+        fn info(&self) -> ImageInfo {
+            ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Premul, None)
+        }
+
+        fn on_get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool {
+            if info.color_type() != ColorType::RGBA8888 {
+                return false;
+            }
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            for y in 0..info.height() as usize {
+                for x in 0..info.width() as usize {
+                    let value = if (x + y) % 2 == 0 { 0xff } else { 0x00 };
+                    let i = y * row_bytes + x * 4;
+                    pixels[i..i + 4].copy_from_slice(&[value, value, value, 0xff]);
+                }
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn rust_generator_is_called_when_the_image_is_drawn() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut generator = ImageGenerator::new(Checkerboard {
+            calls: calls.clone(),
+        });
+        assert_eq!(generator.info().width(), 4);
+        assert!(generator.encoded_data().is_none());
+
+        let image = Image::from_generator(generator).unwrap();
+        assert!(image.is_lazy_generated());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let mut surface = Surface::new_raster_n32_premul((4, 4)).unwrap();
+        surface.canvas().draw_image(&image, (0, 0), None);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let mut bitmap = crate::Bitmap::new();
+        bitmap.alloc_n32_pixels((4, 4), None);
+        assert!(surface.read_pixels_to_bitmap(&bitmap, (0, 0)));
+        assert_eq!(bitmap.get_color((0, 0)), Color::WHITE);
+        assert_eq!(bitmap.get_color((1, 0)), Color::BLACK);
+    }
+
+    struct Encoded(Data);
+
+    impl ImageGeneratorImpl for Encoded {
+        fn info(&self) -> ImageInfo {
+            ImageInfo::new_n32_premul((1, 1), None)
+        }
+
+        fn on_get_pixels(&mut self, _: &ImageInfo, _: &mut [u8], _: usize) -> bool {
+            false
+        }
+
+        fn on_ref_encoded_data(&mut self) -> Option<Data> {
+            Some(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn rust_generator_returns_encoded_data() {
+        let mut generator = ImageGenerator::new(Encoded(Data::new_copy(&[1, 2, 3])));
+        assert_eq!(generator.encoded_data().unwrap().as_bytes(), &[1, 2, 3]);
+    }
+}
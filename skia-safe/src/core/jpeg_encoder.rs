@@ -0,0 +1,85 @@
+use super::image_encoder::{encode_to_data, encode_to_writer};
+use crate::prelude::*;
+use crate::{Data, ImageInfo, Pixmap, RowEncoder};
+use skia_bindings as sb;
+use std::io;
+
+/// The chroma subsampling of the JPEG encoder.
+pub use sb::SkJpegEncoder_Downsample as JpegDownsample;
+
+#[test]
+fn test_jpeg_downsample_naming() {
+    let _ = JpegDownsample::Downsample444;
+}
+
+/// How the JPEG encoder handles transparent pixels.
+pub use sb::SkJpegEncoder_AlphaOption as JpegAlphaOption;
+
+#[test]
+fn test_jpeg_alpha_option_naming() {
+    let _ = JpegAlphaOption::BlendOnBlack;
+}
+
+/// Encodes pixmaps as JPEG.
+///
+/// If the pixmap has a [crate::ColorSpace], its ICC profile is embedded.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct JpegEncoder {
+    /// The quality from `0` to `100`.
+    pub quality: usize,
+    /// [JpegDownsample::Downsample444] keeps the full chroma resolution.
+    pub downsample: JpegDownsample,
+    pub alpha_option: JpegAlphaOption,
+}
+
+impl Default for JpegEncoder {
+    fn default() -> Self {
+        JpegEncoder {
+            quality: 100,
+            downsample: JpegDownsample::Downsample420,
+            alpha_option: JpegAlphaOption::Ignore,
+        }
+    }
+}
+
+impl JpegEncoder {
+    /// Encodes `src`, returns `None` if it can not be encoded.
+    pub fn encode(&self, src: &Pixmap) -> Option<Data> {
+        encode_to_data(|stream| self.encode_native(stream, src))
+    }
+
+    /// Encodes `src` and writes it to `writer`.
+    pub fn encode_to_writer(&self, src: &Pixmap, writer: &mut impl io::Write) -> io::Result<()> {
+        encode_to_writer(writer, |stream| self.encode_native(stream, src))
+    }
+
+    /// Creates an encoder that encodes an image described by `info` row by row and writes it to
+    /// `writer`.
+    pub fn row_encoder<'a>(
+        &self,
+        info: &ImageInfo,
+        writer: &'a mut impl io::Write,
+    ) -> Option<RowEncoder<'a>> {
+        RowEncoder::new(info, writer, |stream, info| unsafe {
+            sb::C_RowEncoder_MakeJpeg(
+                stream,
+                info.native(),
+                self.quality.try_into().unwrap(),
+                self.downsample,
+                self.alpha_option,
+            )
+        })
+    }
+
+    fn encode_native(&self, stream: &mut sb::SkWStream, src: &Pixmap) -> bool {
+        unsafe {
+            sb::C_SkJpegEncoder_Encode(
+                stream,
+                src.native(),
+                self.quality.try_into().unwrap(),
+                self.downsample,
+                self.alpha_option,
+            )
+        }
+    }
+}
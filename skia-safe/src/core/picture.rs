@@ -1,4 +1,7 @@
-use crate::{prelude::*, Canvas, Data, FilterMode, Matrix, Rect, Shader, TileMode};
+use crate::{
+    prelude::*, Canvas, Data, DeserialProcs, FilterMode, Matrix, Rect, SerialProcs, Shader,
+    TileMode,
+};
 use skia_bindings::{self as sb, SkPicture, SkRefCntBase};
 use std::fmt;
 
@@ -24,8 +27,6 @@ impl fmt::Debug for Picture {
 impl Picture {
     // TODO: wrap MakeFromStream
 
-    // TODO: may support SkSerialProces in MakeFromData?
-
     pub fn from_data(data: &Data) -> Option<Picture> {
         Picture::from_ptr(unsafe { sb::C_SkPicture_MakeFromData(data.native()) })
     }
@@ -36,6 +37,14 @@ impl Picture {
         })
     }
 
+    /// Deserializes a picture that was serialized with [Picture::serialize_with_procs()].
+    pub fn from_data_with_procs(data: &Data, procs: &mut DeserialProcs) -> Option<Picture> {
+        let bytes = data.as_bytes();
+        Picture::from_ptr(unsafe {
+            sb::C_SkPicture_MakeFromDataWithProcs(bytes.as_ptr() as _, bytes.len(), &procs.native())
+        })
+    }
+
     // TODO: AbortCallback and the function that use it.
 
     pub fn playback(&self, canvas: &mut Canvas) {
@@ -50,12 +59,19 @@ impl Picture {
         unsafe { sb::C_SkPicture_uniqueID(self.native()) }
     }
 
-    // TODO: support SkSerialProcs in serialize()?
-
     pub fn serialize(&self) -> Data {
         Data::from_ptr(unsafe { sb::C_SkPicture_serialize(self.native()) }).unwrap()
     }
 
+    /// Serializes the picture, the nested pictures, images, and typefaces it refers to are
+    /// serialized by `procs`.
+    pub fn serialize_with_procs(&self, procs: &mut SerialProcs) -> Data {
+        Data::from_ptr(unsafe {
+            sb::C_SkPicture_serializeWithProcs(self.native(), &procs.native())
+        })
+        .unwrap()
+    }
+
     pub fn new_placeholder(cull: impl AsRef<Rect>) -> Picture {
         Picture::from_ptr(unsafe { sb::C_SkPicture_MakePlaceholder(cull.as_ref().native()) })
             .unwrap()
@@ -108,3 +124,46 @@ impl Picture {
         .unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Data, DeserialProcs, Picture, PictureRecorder, Rect, SerialProcs, Surface};
+    use std::collections::HashMap;
+
+    #[test]
+    fn serialize_images_by_reference() {
+        let mut surface = Surface::new_raster_n32_premul((64, 64)).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::from_wh(64.0, 64.0), None);
+        canvas.draw_image(&image, (0, 0), None);
+        canvas.draw_image(&image, (32, 32), None);
+        let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+        let mut images = HashMap::new();
+        let mut serial_procs = SerialProcs::default();
+        serial_procs.set_image_proc(|image| {
+            images.insert(image.unique_id(), image.clone());
+            Some(Data::new_copy(&image.unique_id().to_le_bytes()))
+        });
+        let serialized = picture.serialize_with_procs(&mut serial_procs);
+        drop(serial_procs);
+        assert_eq!(images.len(), 1);
+        assert!(serialized.size() < picture.serialize().size());
+
+        let mut lookups = 0;
+        let mut deserial_procs = DeserialProcs::default();
+        deserial_procs.set_image_proc(|data| {
+            lookups += 1;
+            let mut id = [0u8; 4];
+            id.copy_from_slice(data);
+            images.get(&u32::from_le_bytes(id)).cloned()
+        });
+        let deserialized = Picture::from_data_with_procs(&serialized, &mut deserial_procs);
+        drop(deserial_procs);
+        assert_eq!(deserialized.unwrap().approximate_op_count(), 2);
+        assert!(lookups > 0);
+    }
+}
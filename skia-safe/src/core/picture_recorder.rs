@@ -1,4 +1,4 @@
-use crate::{prelude::*, BBHFactory, Canvas, Drawable, Picture, Rect};
+use crate::{prelude::*, BBHFactory, BBoxHierarchy, Canvas, Drawable, Picture, Rect};
 use skia_bindings::{self as sb, SkPictureRecorder, SkRect};
 use std::{fmt, ptr};
 
@@ -23,8 +23,6 @@ impl PictureRecorder {
         Self::construct(|pr| unsafe { sb::C_SkPictureRecorder_Construct(pr) })
     }
 
-    // TODO: beginRecording with BBoxHierarchy
-
     pub fn begin_recording(
         &mut self,
         bounds: impl AsRef<Rect>,
@@ -40,6 +38,32 @@ impl PictureRecorder {
         Canvas::borrow_from_native_mut(canvas_ref)
     }
 
+    /// Begins recording into `bbh`, which can be queried for the operations of the picture
+    /// afterwards.
+    ///
+    /// When the picture is played back, only the operations that intersect the canvas's clip
+    /// are drawn.
+    ///
+    /// # Safety
+    ///
+    /// `bbh` must be empty and must not be used to record another picture or be filled with
+    /// [BBoxHierarchy::insert()] while the picture is alive: the picture plays back the
+    /// operations at the indices `bbh` returns, which may be out of bounds of its operations
+    /// otherwise.
+    pub unsafe fn begin_recording_with_bbh(
+        &mut self,
+        bounds: impl AsRef<Rect>,
+        bbh: &BBoxHierarchy,
+    ) -> &mut Canvas {
+        let canvas_ref = &mut *sb::C_SkPictureRecorder_beginRecordingWithBBH(
+            self.native_mut(),
+            bounds.as_ref().native(),
+            bbh.clone().into_ptr(),
+        );
+
+        Canvas::borrow_from_native_mut(canvas_ref)
+    }
+
     pub fn recording_canvas(&mut self) -> Option<&mut Canvas> {
         let canvas = unsafe { self.native_mut().getRecordingCanvas() };
         if canvas.is_null() {
@@ -97,6 +121,44 @@ fn finishing_recording_two_times() {
     assert!(recorder.finish_recording_as_picture(None).is_none());
 }
 
+#[test]
+fn record_with_rtree() {
+    let mut recorder = PictureRecorder::new();
+    let mut factory = BBHFactory::new_rtree();
+    let canvas = recorder.begin_recording(&Rect::new(0.0, 0.0, 100.0, 100.0), Some(&mut factory));
+    canvas.draw_rect(Rect::new(0.0, 0.0, 10.0, 10.0), &crate::Paint::default());
+    assert!(recorder.finish_recording_as_picture(None).is_some());
+}
+
+#[test]
+fn record_with_bbh_and_search() {
+    let bbh = BBoxHierarchy::new_rtree();
+    let mut recorder = PictureRecorder::new();
+    let canvas =
+        unsafe { recorder.begin_recording_with_bbh(&Rect::new(0.0, 0.0, 1000.0, 1000.0), &bbh) };
+    let paint = crate::Paint::default();
+    for i in 0..10 {
+        let offset = i as f32 * 100.0;
+        canvas.draw_rect(Rect::from_xywh(offset, offset, 10.0, 10.0), &paint);
+    }
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+    assert_eq!(picture.approximate_op_count(), 10);
+    assert_eq!(
+        bbh.search(Rect::from_xywh(295.0, 295.0, 10.0, 10.0)).len(),
+        1
+    );
+
+    // Only the operations that intersect the clip are played back.
+    let mut clipped = PictureRecorder::new();
+    let canvas = clipped.begin_recording(&Rect::new(0.0, 0.0, 50.0, 50.0), None);
+    canvas.clip_rect(Rect::new(0.0, 0.0, 50.0, 50.0), None, None);
+    canvas.translate((-300.0, -300.0));
+    picture.playback(canvas);
+    let played_back = clipped.finish_recording_as_picture(None).unwrap();
+    // Without culling, all 10 rects would be recorded in addition to the clip and translate.
+    assert!(played_back.approximate_op_count() < picture.approximate_op_count());
+}
+
 #[test]
 fn not_recording_no_canvas() {
     let mut recorder = PictureRecorder::new();
@@ -1,4 +1,5 @@
-use crate::{prelude::*, ISize};
+use super::bitmap::release_owned_pixels;
+use crate::{prelude::*, ISize, ImageInfo};
 use skia_bindings::{self as sb, SkPixelRef, SkRefCntBase};
 use std::{fmt, os::raw::c_void};
 
@@ -24,6 +25,75 @@ impl fmt::Debug for PixelRef {
 impl PixelRef {
     // TODO: wrap constructor with pixels borrowed.
 
+    /// Creates a [PixelRef] with the dimensions of `info` that takes ownership of `pixels`, for
+    /// example a `Vec<u8>`, or a memory-mapped file. `pixels` are dropped when the [PixelRef] is
+    /// released.
+    ///
+    /// `row_bytes` defaults to `info.min_row_bytes()`.
+    ///
+    /// Returns `None` and drops `pixels` if `row_bytes` is less than `info.min_row_bytes()`, or
+    /// `pixels` are too small to hold the pixels described by `info`.
+    pub fn new_owned<P>(
+        info: &ImageInfo,
+        pixels: P,
+        row_bytes: impl Into<Option<usize>>,
+    ) -> Option<PixelRef>
+    where
+        P: AsMut<[u8]> + Send + 'static,
+    {
+        let row_bytes = row_bytes.into().unwrap_or_else(|| info.min_row_bytes());
+        if info.is_empty() || !info.valid_row_bytes(row_bytes) {
+            return None;
+        }
+        let mut pixels = Box::new(pixels);
+        let addr = {
+            let pixels = (*pixels).as_mut();
+            if pixels.len() < info.compute_byte_size(row_bytes) {
+                return None;
+            }
+            pixels.as_mut_ptr()
+        };
+        PixelRef::from_ptr(unsafe {
+            sb::C_RustPixelRef_New(
+                info.width(),
+                info.height(),
+                addr as _,
+                row_bytes,
+                Some(release_owned_pixels::<P>),
+                Box::into_raw(pixels) as _,
+            )
+        })
+    }
+
+    /// Creates a [PixelRef] of `dimensions` over the memory at `pixels`, for example a
+    /// shared-memory segment. `release` is called with `pixels` when the [PixelRef] is released.
+    ///
+    /// # Safety
+    ///
+    /// `pixels` must point to at least `row_bytes * dimensions.height` bytes that stay valid
+    /// until `release` is called. `row_bytes` must be large enough to hold a row of the bitmaps
+    /// the [PixelRef] is installed in.
+    pub unsafe fn from_raw_with_release_proc<F>(
+        dimensions: impl Into<ISize>,
+        pixels: *mut c_void,
+        row_bytes: usize,
+        release: F,
+    ) -> PixelRef
+    where
+        F: FnOnce(*mut c_void) + Send + 'static,
+    {
+        let dimensions = dimensions.into();
+        PixelRef::from_ptr(sb::C_RustPixelRef_New(
+            dimensions.width,
+            dimensions.height,
+            pixels,
+            row_bytes,
+            Some(call_release_proc::<F>),
+            Box::into_raw(Box::new(release)) as _,
+        ))
+        .unwrap()
+    }
+
     pub fn dimensions(&self) -> ISize {
         ISize::new(self.width(), self.height())
     }
@@ -67,3 +137,56 @@ impl PixelRef {
         unsafe { sb::C_SkPixelRef_notifyAddedToCache(self.native_mut()) }
     }
 }
+
+unsafe extern "C" fn call_release_proc<F: FnOnce(*mut c_void)>(
+    pixels: *mut c_void,
+    context: *mut c_void,
+) {
+    Box::from_raw(context as *mut F)(pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PixelRef;
+    use crate::{Bitmap, Color, ImageInfo};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn new_owned() {
+        let info = ImageInfo::new_n32_premul((4, 4), None);
+        let pixel_ref = PixelRef::new_owned(&info, vec![0u8; 64], None).unwrap();
+        assert_eq!(pixel_ref.dimensions(), info.dimensions());
+        assert_eq!(pixel_ref.row_bytes(), 16);
+
+        let mut bitmap = Bitmap::new();
+        assert!(bitmap.set_info(&info, None));
+        bitmap.set_pixel_ref(pixel_ref, (0, 0));
+        bitmap.erase_color(Color::BLUE);
+        assert_eq!(bitmap.get_color((2, 2)), Color::BLUE);
+
+        assert!(PixelRef::new_owned(&info, vec![0u8; 63], None).is_none());
+        assert!(PixelRef::new_owned(&info, vec![0u8; 64], 15).is_none());
+    }
+
+    #[test]
+    fn release_proc_is_called() {
+        let mut pixels = vec![0u8; 16];
+        let addr = pixels.as_mut_ptr() as usize;
+        let released = Arc::new(AtomicBool::new(false));
+        let pixel_ref = {
+            let released = released.clone();
+            unsafe {
+                PixelRef::from_raw_with_release_proc((2, 2), addr as _, 8, move |p| {
+                    assert_eq!(p as usize, addr);
+                    released.store(true, Ordering::SeqCst);
+                })
+            }
+        };
+        assert!(!released.load(Ordering::SeqCst));
+        drop(pixel_ref);
+        assert!(released.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,128 @@
+use super::image_encoder::{encode_to_data, encode_to_writer};
+use crate::prelude::*;
+use crate::{Data, ImageInfo, Pixmap, RowEncoder};
+use skia_bindings as sb;
+use std::{ffi, io, os::raw};
+
+bitflags! {
+    /// The row filters the PNG encoder may choose from.
+    pub struct PngFilterFlags: u32 {
+        const ZERO = sb::SkPngEncoder_FilterFlag_kZero as _;
+        const NONE = sb::SkPngEncoder_FilterFlag_kNone as _;
+        const SUB = sb::SkPngEncoder_FilterFlag_kSub as _;
+        const UP = sb::SkPngEncoder_FilterFlag_kUp as _;
+        const AVG = sb::SkPngEncoder_FilterFlag_kAvg as _;
+        const PAETH = sb::SkPngEncoder_FilterFlag_kPaeth as _;
+        const ALL = sb::SkPngEncoder_FilterFlag_kAll as _;
+    }
+}
+
+impl Default for PngFilterFlags {
+    fn default() -> Self {
+        PngFilterFlags::ALL
+    }
+}
+
+/// Encodes pixmaps as PNG.
+///
+/// If the pixmap has a [crate::ColorSpace], its ICC profile is embedded.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PngEncoder {
+    /// The row filters to try, more filters may produce smaller files, but take longer to
+    /// encode.
+    pub filter_flags: PngFilterFlags,
+    /// The zlib compression level, from `0` (no compression) to `9` (best compression).
+    /// Levels outside of this range are clamped.
+    pub zlib_level: i32,
+    /// Text chunks as keyword and text pairs, neither of them must contain a null character.
+    pub comments: Vec<(String, String)>,
+}
+
+impl Default for PngEncoder {
+    fn default() -> Self {
+        PngEncoder {
+            filter_flags: PngFilterFlags::ALL,
+            zlib_level: 6,
+            comments: Vec::new(),
+        }
+    }
+}
+
+impl PngEncoder {
+    /// Encodes `src`, returns `None` if it can not be encoded or a comment contains a null
+    /// character.
+    pub fn encode(&self, src: &Pixmap) -> Option<Data> {
+        let comments = self.comments()?;
+        encode_to_data(|stream| self.encode_native(stream, src, &comments))
+    }
+
+    /// Encodes `src` and writes it to `writer`.
+    ///
+    /// Returns an [io::ErrorKind::InvalidInput] error if a comment contains a null character.
+    pub fn encode_to_writer(&self, src: &Pixmap, writer: &mut impl io::Write) -> io::Result<()> {
+        let comments = self.comments().ok_or_else(invalid_comments)?;
+        encode_to_writer(writer, |stream| self.encode_native(stream, src, &comments))
+    }
+
+    /// Creates an encoder that encodes an image described by `info` row by row and writes it to
+    /// `writer`.
+    ///
+    /// Returns `None` if the image can not be encoded or a comment contains a null character.
+    pub fn row_encoder<'a>(
+        &self,
+        info: &ImageInfo,
+        writer: &'a mut impl io::Write,
+    ) -> Option<RowEncoder<'a>> {
+        let comments = self.comments()?;
+        let comment_ptrs: Vec<*const raw::c_char> = comments.iter().map(|s| s.as_ptr()).collect();
+        RowEncoder::new(info, writer, |stream, info| unsafe {
+            sb::C_RowEncoder_MakePng(
+                stream,
+                info.native(),
+                self.filter_flags.bits() as _,
+                self.clamped_zlib_level(),
+                comment_ptrs.as_ptr(),
+                comment_ptrs.len(),
+            )
+        })
+    }
+
+    fn clamped_zlib_level(&self) -> i32 {
+        self.zlib_level.max(0).min(9)
+    }
+
+    /// The keywords and texts of the comments, `None` if one of them contains a null character.
+    fn comments(&self) -> Option<Vec<ffi::CString>> {
+        self.comments
+            .iter()
+            .flat_map(|(keyword, text)| vec![keyword, text])
+            .map(|s| ffi::CString::new(s.as_str()).ok())
+            .collect()
+    }
+
+    fn encode_native(
+        &self,
+        stream: &mut sb::SkWStream,
+        src: &Pixmap,
+        comments: &[ffi::CString],
+    ) -> bool {
+        let comment_ptrs: Vec<*const raw::c_char> = comments.iter().map(|s| s.as_ptr()).collect();
+        unsafe {
+            sb::C_SkPngEncoder_Encode(
+                stream,
+                src.native(),
+                self.filter_flags.bits() as _,
+                self.clamped_zlib_level(),
+                comment_ptrs.as_ptr(),
+                comment_ptrs.len(),
+            )
+        }
+    }
+}
+
+fn invalid_comments() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "PNG comments must not contain null characters",
+    )
+}
@@ -1 +1,175 @@
-// TODO
+use crate::{prelude::*, IRect, ImageInfo, Matrix};
+use skia_bindings::{
+    RustRasterHandleAllocator_Param, SkIRect, SkImageInfo, SkMatrix, SkRasterHandleAllocator_Rec,
+};
+use std::{ffi, fmt, mem};
+
+/// An opaque handle of a native graphics library that draws into the pixels of a layer, see
+/// [RasterHandleAllocator].
+pub type RasterHandle = *mut ffi::c_void;
+
+/// Allocates the pixel memory of the layers of a raster [crate::Canvas] together with a
+/// [RasterHandle], so that another graphics library can draw into the same pixels, see
+/// [crate::Canvas::from_raster_handle_allocator()].
+pub trait RasterHandleAllocator {
+    /// Allocates the pixels and the handle of a layer described by `info`.
+    ///
+    /// Returns `None` if the layer can not be allocated.
+    fn alloc_handle(&mut self, info: &ImageInfo) -> Option<RasterHandleAllocation>;
+
+    /// Updates `handle` to the current `matrix` and `clip_bounds` of the canvas, before the
+    /// handle is returned by [crate::Canvas::access_top_raster_handle()].
+    fn update_handle(&mut self, handle: RasterHandle, matrix: &Matrix, clip_bounds: &IRect);
+}
+
+/// The pixels and the handle of a layer allocated by [RasterHandleAllocator::alloc_handle()].
+pub struct RasterHandleAllocation {
+    pixels: *mut ffi::c_void,
+    len: usize,
+    row_bytes: usize,
+    handle: RasterHandle,
+    release: Box<dyn FnOnce()>,
+}
+
+impl fmt::Debug for RasterHandleAllocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RasterHandleAllocation")
+            .field("pixels", &self.pixels)
+            .field("len", &self.len)
+            .field("row_bytes", &self.row_bytes)
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+impl RasterHandleAllocation {
+    /// Creates an allocation that owns `pixels`, which are dropped when the layer is released.
+    ///
+    /// `row_bytes` defaults to `info.min_row_bytes()`.
+    ///
+    /// Returns `None` if `pixels` are too small to hold the pixels described by `info`.
+    pub fn new_owned<P>(
+        info: &ImageInfo,
+        pixels: P,
+        row_bytes: impl Into<Option<usize>>,
+        handle: RasterHandle,
+    ) -> Option<Self>
+    where
+        P: AsMut<[u8]> + 'static,
+    {
+        let row_bytes = row_bytes.into().unwrap_or_else(|| info.min_row_bytes());
+        let mut pixels = Box::new(pixels);
+        let (addr, len) = {
+            let pixels = (*pixels).as_mut();
+            if !info.valid_pixels(row_bytes, pixels) {
+                return None;
+            }
+            (pixels.as_mut_ptr(), pixels.len())
+        };
+        Some(RasterHandleAllocation {
+            pixels: addr as _,
+            len,
+            row_bytes,
+            handle,
+            release: Box::new(move || drop(pixels)),
+        })
+    }
+
+    /// Creates an allocation of the `len` bytes at `pixels`, `release` is called when the layer
+    /// is released.
+    ///
+    /// # Safety
+    ///
+    /// `pixels` must point to `len` writable bytes that stay valid until `release` is called.
+    pub unsafe fn from_raw(
+        pixels: *mut ffi::c_void,
+        len: usize,
+        row_bytes: usize,
+        handle: RasterHandle,
+        release: impl FnOnce() + 'static,
+    ) -> Self {
+        RasterHandleAllocation {
+            pixels,
+            len,
+            row_bytes,
+            handle,
+            release: Box::new(release),
+        }
+    }
+
+    /// Returns the number of bytes between the starts of two consecutive rows of the pixels.
+    pub fn row_bytes(&self) -> usize {
+        self.row_bytes
+    }
+
+    /// Returns the handle that draws into the pixels of the layer.
+    pub fn handle(&self) -> RasterHandle {
+        self.handle
+    }
+}
+
+/// Returns the parameters of a native raster handle allocator that takes ownership of
+/// `allocator`.
+pub(crate) fn native_raster_handle_allocator<T: RasterHandleAllocator + 'static>(
+    allocator: T,
+) -> RustRasterHandleAllocator_Param {
+    RustRasterHandleAllocator_Param {
+        data: Box::into_raw(Box::new(allocator)) as _,
+        drop: Some(drop_allocator::<T>),
+        allocHandle: Some(alloc_handle::<T>),
+        updateHandle: Some(update_handle::<T>),
+    }
+}
+
+unsafe extern "C" fn drop_allocator<T>(data: *mut ffi::c_void) {
+    mem::drop(Box::from_raw(data as *mut T))
+}
+
+unsafe extern "C" fn alloc_handle<T: RasterHandleAllocator>(
+    data: *mut ffi::c_void,
+    info: *const SkImageInfo,
+    rec: *mut SkRasterHandleAllocator_Rec,
+) -> bool {
+    let allocator = &mut *(data as *mut T);
+    let info = ImageInfo::from_native_ref(&*info);
+    match allocator.alloc_handle(info) {
+        // The allocation may have been created for a different `info`, Skia would then draw
+        // outside of its pixels.
+        Some(allocation)
+            if allocation.row_bytes < info.min_row_bytes()
+                || allocation.len < info.compute_byte_size(allocation.row_bytes) =>
+        {
+            (allocation.release)();
+            false
+        }
+        Some(allocation) => {
+            let rec = &mut *rec;
+            rec.fReleaseProc = Some(release_allocation);
+            rec.fReleaseCtx = Box::into_raw(Box::new(allocation.release)) as _;
+            rec.fPixels = allocation.pixels;
+            rec.fRowBytes = allocation.row_bytes;
+            rec.fHandle = allocation.handle;
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe extern "C" fn update_handle<T: RasterHandleAllocator>(
+    data: *mut ffi::c_void,
+    handle: RasterHandle,
+    matrix: *const SkMatrix,
+    clip_bounds: *const SkIRect,
+) {
+    let allocator = &mut *(data as *mut T);
+    allocator.update_handle(
+        handle,
+        Matrix::from_native_ref(&*matrix),
+        IRect::from_native_ref(&*clip_bounds),
+    )
+}
+
+unsafe extern "C" fn release_allocation(_pixels: *mut ffi::c_void, context: *mut ffi::c_void) {
+    let release = Box::from_raw(context as *mut Box<dyn FnOnce()>);
+    release()
+}
@@ -0,0 +1,91 @@
+use crate::interop::RustWStream;
+use crate::prelude::*;
+use crate::{ImageInfo, Pixmap};
+use skia_bindings::{self as sb, RowEncoder as NativeRowEncoder, SkWStream};
+use std::{fmt, io, pin::Pin};
+
+/// Encodes an image row by row and writes it to a Rust writer.
+///
+/// Created by [crate::PngEncoder::row_encoder()] and [crate::JpegEncoder::row_encoder()]. The
+/// rows are provided in bands and the encoded image is written while they are encoded, so its
+/// encoded form does not need to be held in memory. The native encoder copies each band into a
+/// buffer of the size of the largest band, but does not keep the rows of previous bands. The
+/// encoded image is complete after all rows were encoded.
+pub struct RowEncoder<'a> {
+    native: RefHandle<NativeRowEncoder>,
+    // The native encoder writes to the stream, so it must be dropped after the encoder.
+    stream: Pin<Box<RustWStream<'a>>>,
+    info: ImageInfo,
+    rows_encoded: usize,
+}
+
+impl NativeDrop for NativeRowEncoder {
+    fn drop(&mut self) {
+        unsafe { sb::C_RowEncoder_delete(self) }
+    }
+}
+
+impl fmt::Debug for RowEncoder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RowEncoder")
+            .field("info", &self.info)
+            .field("rows_encoded", &self.rows_encoded)
+            .finish()
+    }
+}
+
+impl<'a> RowEncoder<'a> {
+    pub(crate) fn new(
+        info: &ImageInfo,
+        writer: &'a mut impl io::Write,
+        make: impl FnOnce(&mut SkWStream, &ImageInfo) -> *mut NativeRowEncoder,
+    ) -> Option<Self> {
+        let mut stream = Box::pin(RustWStream::new(writer));
+        let native = RefHandle::from_ptr(make(stream.stream_mut(), info))?;
+        Some(RowEncoder {
+            native,
+            stream,
+            info: info.clone(),
+            rows_encoded: 0,
+        })
+    }
+
+    /// The image that is encoded.
+    pub fn info(&self) -> &ImageInfo {
+        &self.info
+    }
+
+    pub fn rows_encoded(&self) -> usize {
+        self.rows_encoded
+    }
+
+    /// Whether all rows of the image were encoded.
+    pub fn is_finished(&self) -> bool {
+        self.rows_encoded == self.info.height() as usize
+    }
+
+    /// Encodes the next `rows.height()` rows of the image.
+    ///
+    /// The width, color type, and alpha type of `rows` must match the image's.
+    pub fn encode_rows(&mut self, rows: &Pixmap) -> io::Result<()> {
+        let info = rows.info();
+        let rows_remaining = self.info.height() as usize - self.rows_encoded;
+        if info.width() != self.info.width()
+            || info.color_type() != self.info.color_type()
+            || info.alpha_type() != self.info.alpha_type()
+            || info.height() as usize > rows_remaining
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the rows do not match the remaining rows of the image",
+            ));
+        }
+        if !unsafe { sb::C_RowEncoder_encodeRows(self.native.native_mut(), rows.native()) } {
+            return Err(self.stream.take_error().unwrap_or_else(|| {
+                io::Error::new(io::ErrorKind::Other, "failed to encode the rows")
+            }));
+        }
+        self.rows_encoded += info.height() as usize;
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use crate::prelude::*;
+use crate::{Data, Image, Picture, Typeface};
+use skia_bindings::{RustDeserialProcs, RustSerialProcs, SkData, SkImage, SkPicture, SkTypeface};
+use std::{ffi, fmt, slice};
+
+type SerialProc<'a, T> = Box<dyn FnMut(&T) -> Option<Data> + 'a>;
+type DeserialProc<'a, T> = Box<dyn FnMut(&[u8]) -> Option<T> + 'a>;
+
+/// Closures that replace how pictures, images, and typefaces are serialized.
+///
+/// A closure that returns `None` lets Skia serialize the object, which embeds images as encoded
+/// data and typefaces with their font data.
+#[derive(Default)]
+pub struct SerialProcs<'a> {
+    picture: Option<SerialProc<'a, Picture>>,
+    image: Option<SerialProc<'a, Image>>,
+    typeface: Option<SerialProc<'a, Typeface>>,
+}
+
+impl fmt::Debug for SerialProcs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerialProcs")
+            .field("picture", &self.picture.is_some())
+            .field("image", &self.image.is_some())
+            .field("typeface", &self.typeface.is_some())
+            .finish()
+    }
+}
+
+impl<'a> SerialProcs<'a> {
+    /// Sets the closure that serializes pictures.
+    ///
+    /// It is also invoked for the picture that gets serialized itself.
+    pub fn set_picture_proc(
+        &mut self,
+        proc: impl FnMut(&Picture) -> Option<Data> + 'a,
+    ) -> &mut Self {
+        self.picture = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_image_proc(&mut self, proc: impl FnMut(&Image) -> Option<Data> + 'a) -> &mut Self {
+        self.image = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_typeface_proc(
+        &mut self,
+        proc: impl FnMut(&Typeface) -> Option<Data> + 'a,
+    ) -> &mut Self {
+        self.typeface = Some(Box::new(proc));
+        self
+    }
+
+    /// The native procs, which refer to `self`.
+    pub(crate) fn native(&mut self) -> RustSerialProcs {
+        RustSerialProcs {
+            ctx: self as *mut Self as *mut ffi::c_void,
+            picture: Some(serial_picture),
+            image: Some(serial_image),
+            typeface: Some(serial_typeface),
+        }
+    }
+}
+
+unsafe extern "C" fn serial_picture(ctx: *mut ffi::c_void, picture: *mut SkPicture) -> *mut SkData {
+    let procs = &mut *(ctx as *mut SerialProcs);
+    let picture = Picture::from_unshared_ptr_ref(&picture).as_ref().unwrap();
+    procs
+        .picture
+        .as_mut()
+        .and_then(|proc| proc(picture))
+        .into_ptr_or_null()
+}
+
+unsafe extern "C" fn serial_image(ctx: *mut ffi::c_void, image: *mut SkImage) -> *mut SkData {
+    let procs = &mut *(ctx as *mut SerialProcs);
+    let image = Image::from_unshared_ptr_ref(&image).as_ref().unwrap();
+    procs
+        .image
+        .as_mut()
+        .and_then(|proc| proc(image))
+        .into_ptr_or_null()
+}
+
+unsafe extern "C" fn serial_typeface(
+    ctx: *mut ffi::c_void,
+    typeface: *mut SkTypeface,
+) -> *mut SkData {
+    let procs = &mut *(ctx as *mut SerialProcs);
+    let typeface = Typeface::from_unshared_ptr_ref(&typeface).as_ref().unwrap();
+    procs
+        .typeface
+        .as_mut()
+        .and_then(|proc| proc(typeface))
+        .into_ptr_or_null()
+}
+
+/// Closures that restore the pictures, images, and typefaces that were serialized by
+/// [SerialProcs].
+///
+/// The image closure is invoked for all images, if it returns `None`, Skia decodes the data.
+/// The picture and typeface closures are only invoked for data that was produced by the
+/// corresponding [SerialProcs] closures.
+#[derive(Default)]
+pub struct DeserialProcs<'a> {
+    picture: Option<DeserialProc<'a, Picture>>,
+    image: Option<DeserialProc<'a, Image>>,
+    typeface: Option<DeserialProc<'a, Typeface>>,
+}
+
+impl fmt::Debug for DeserialProcs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeserialProcs")
+            .field("picture", &self.picture.is_some())
+            .field("image", &self.image.is_some())
+            .field("typeface", &self.typeface.is_some())
+            .finish()
+    }
+}
+
+impl<'a> DeserialProcs<'a> {
+    pub fn set_picture_proc(
+        &mut self,
+        proc: impl FnMut(&[u8]) -> Option<Picture> + 'a,
+    ) -> &mut Self {
+        self.picture = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_image_proc(&mut self, proc: impl FnMut(&[u8]) -> Option<Image> + 'a) -> &mut Self {
+        self.image = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_typeface_proc(
+        &mut self,
+        proc: impl FnMut(&[u8]) -> Option<Typeface> + 'a,
+    ) -> &mut Self {
+        self.typeface = Some(Box::new(proc));
+        self
+    }
+
+    /// The native procs, which refer to `self`.
+    pub(crate) fn native(&mut self) -> RustDeserialProcs {
+        RustDeserialProcs {
+            ctx: self as *mut Self as *mut ffi::c_void,
+            picture: Some(deserial_picture),
+            image: Some(deserial_image),
+            typeface: Some(deserial_typeface),
+        }
+    }
+}
+
+unsafe fn deserial_data<'a>(data: *const ffi::c_void, length: usize) -> &'a [u8] {
+    if length == 0 {
+        return &[];
+    }
+    slice::from_raw_parts(data as *const u8, length)
+}
+
+unsafe extern "C" fn deserial_picture(
+    ctx: *mut ffi::c_void,
+    data: *const ffi::c_void,
+    length: usize,
+) -> *mut SkPicture {
+    let procs = &mut *(ctx as *mut DeserialProcs);
+    let data = deserial_data(data, length);
+    procs
+        .picture
+        .as_mut()
+        .and_then(|proc| proc(data))
+        .into_ptr_or_null()
+}
+
+unsafe extern "C" fn deserial_image(
+    ctx: *mut ffi::c_void,
+    data: *const ffi::c_void,
+    length: usize,
+) -> *mut SkImage {
+    let procs = &mut *(ctx as *mut DeserialProcs);
+    let data = deserial_data(data, length);
+    procs
+        .image
+        .as_mut()
+        .and_then(|proc| proc(data))
+        .into_ptr_or_null()
+}
+
+unsafe extern "C" fn deserial_typeface(
+    ctx: *mut ffi::c_void,
+    data: *const ffi::c_void,
+    length: usize,
+) -> *mut SkTypeface {
+    let procs = &mut *(ctx as *mut DeserialProcs);
+    let data = deserial_data(data, length);
+    procs
+        .typeface
+        .as_mut()
+        .and_then(|proc| proc(data))
+        .into_ptr_or_null()
+}
@@ -1,8 +1,10 @@
+use super::bitmap::release_owned_pixels;
 #[cfg(feature = "gpu")]
 use crate::gpu;
 use crate::{
-    prelude::*, Bitmap, Canvas, DeferredDisplayList, IPoint, IRect, ISize, IVector, Image,
-    ImageInfo, Paint, Pixmap, Point, SamplingOptions, SurfaceCharacterization, SurfaceProps,
+    image, prelude::*, Bitmap, Canvas, ColorSpace, DeferredDisplayList, IPoint, IRect, ISize,
+    IVector, Image, ImageInfo, Paint, Pixmap, Point, SamplingOptions, SurfaceCharacterization,
+    SurfaceProps, YUVColorSpace,
 };
 use skia_bindings::{self as sb, SkRefCntBase, SkSurface};
 use std::{fmt, ptr};
@@ -25,6 +27,8 @@ fn test_surface_backend_surface_access_naming() {
     let _ = BackendSurfaceAccess::Present;
 }
 
+pub use crate::image::{AsyncReadResult, RescaleGamma, RescaleMode};
+
 pub type Surface = RCHandle<SkSurface>;
 
 impl NativeRefCountedBase for SkSurface {
@@ -68,8 +72,50 @@ impl Surface {
         .map(move |surface| surface.borrows(pixels))
     }
 
+    /// Creates a [Surface] that draws into `pixels` and takes ownership of them. `pixels` are
+    /// dropped when the surface is released.
+    ///
+    /// Returns `None` and drops `pixels` if the parameters are invalid, or `pixels` are too small
+    /// to hold the pixels described by `image_info`.
+    pub fn new_raster_direct_owned<P>(
+        image_info: &ImageInfo,
+        pixels: P,
+        row_bytes: impl Into<Option<usize>>,
+        surface_props: Option<&SurfaceProps>,
+    ) -> Option<Surface>
+    where
+        P: AsMut<[u8]> + Send + 'static,
+    {
+        let row_bytes = row_bytes
+            .into()
+            .unwrap_or_else(|| image_info.min_row_bytes());
+        let mut pixels = Box::new(pixels);
+        let addr = {
+            let pixels = (*pixels).as_mut();
+            if pixels.len() < image_info.compute_byte_size(row_bytes) {
+                return None;
+            }
+            pixels.as_mut_ptr()
+        };
+        let context = Box::into_raw(pixels);
+        let surface = Self::from_ptr(unsafe {
+            sb::C_SkSurface_MakeRasterDirectReleaseProc(
+                image_info.native(),
+                addr as _,
+                row_bytes,
+                Some(release_owned_pixels::<P>),
+                context as _,
+                surface_props.native_ptr_or_null(),
+            )
+        });
+        // The release proc is not called if the surface can not be created.
+        if surface.is_none() {
+            drop(unsafe { Box::from_raw(context) });
+        }
+        surface
+    }
+
     // TODO: MakeRasterDirect(&Pixmap)
-    // TODO: MakeRasterDirectReleaseProc()?
 
     pub fn new_raster(
         image_info: &ImageInfo,
@@ -436,9 +482,66 @@ impl Surface {
         unsafe { self.native_mut().readPixels2(bitmap.native(), src.x, src.y) }
     }
 
-    // TODO: AsyncReadResult, RescaleGamma (m79, m86)
-    // TODO: wrap asyncRescaleAndReadPixels (m76, m79, m89)
-    // TODO: wrap asyncRescaleAndReadPixelsYUV420 (m77, m79, m89)
+    /// Reads the pixels of `src_rect`, rescaled to the dimensions of `info` and converted to its
+    /// color type, alpha type, and color space, and passes them to `callback`.
+    ///
+    /// See [Image::async_rescale_and_read_pixels()].
+    pub fn async_rescale_and_read_pixels(
+        &mut self,
+        info: &ImageInfo,
+        src_rect: impl AsRef<IRect>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + Send + 'static,
+    ) {
+        let (callback, context) =
+            image::read_pixels_callback(vec![info.height().try_into().unwrap()], callback);
+        unsafe {
+            sb::C_SkSurface_asyncRescaleAndReadPixels(
+                self.native_mut(),
+                info.native(),
+                src_rect.as_ref().native(),
+                rescale_gamma,
+                rescale_mode,
+                callback,
+                context,
+            )
+        }
+    }
+
+    /// Reads the pixels of `src_rect`, rescaled to `dst_size` and converted to the Y, U, and V
+    /// planes of `yuv_color_space` in `dst_color_space`, and passes them to `callback`.
+    ///
+    /// YUV420 reads are only supported on the GPU backend, on raster surfaces, `callback` always
+    /// receives `None`. See [Image::async_rescale_and_read_pixels_yuv420()].
+    #[allow(clippy::too_many_arguments)]
+    pub fn async_rescale_and_read_pixels_yuv420(
+        &mut self,
+        yuv_color_space: YUVColorSpace,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+        src_rect: impl AsRef<IRect>,
+        dst_size: impl Into<ISize>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + Send + 'static,
+    ) {
+        let dst_size = dst_size.into();
+        let (callback, context) =
+            image::read_pixels_callback(image::yuv420_plane_heights(dst_size.height), callback);
+        unsafe {
+            sb::C_SkSurface_asyncRescaleAndReadPixelsYUV420(
+                self.native_mut(),
+                yuv_color_space,
+                dst_color_space.into().into_ptr_or_null(),
+                src_rect.as_ref().native(),
+                dst_size.native(),
+                rescale_gamma,
+                rescale_mode,
+                callback,
+                context,
+            )
+        }
+    }
 
     pub fn write_pixels_from_pixmap(&mut self, src: &Pixmap, dst: impl Into<IPoint>) {
         let dst = dst.into();
@@ -578,3 +681,66 @@ fn test_drawing_owned_as_exclusive_ref_ergonomics() {
         surface.draw(canvas, (10.0, 10.0), SamplingOptions::default(), None);
     }
 }
+
+#[test]
+fn test_raster_direct_owned() {
+    let info = ImageInfo::new_n32_premul((4, 4), None);
+    let pixels = vec![0u8; info.compute_min_byte_size()];
+    let mut surface = Surface::new_raster_direct_owned(&info, pixels, None, None).unwrap();
+    surface.canvas().clear(crate::Color::RED);
+    let pixmap = surface.peek_pixels().unwrap();
+    assert_eq!(pixmap.get_color((3, 3)), crate::Color::RED);
+
+    let too_small = vec![0u8; info.compute_min_byte_size() - 1];
+    assert!(Surface::new_raster_direct_owned(&info, too_small, None, None).is_none());
+}
+
+#[test]
+fn test_async_rescale_and_read_pixels() {
+    use std::sync::{Arc, Mutex};
+
+    let mut surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+    surface.canvas().clear(crate::Color::WHITE);
+    let info = ImageInfo::new_n32_premul((8, 8), None);
+    let read = Arc::new(Mutex::new(None));
+    {
+        let read = read.clone();
+        surface.async_rescale_and_read_pixels(
+            &info,
+            IRect::from_wh(16, 16),
+            RescaleGamma::Linear,
+            RescaleMode::RepeatedLinear,
+            move |result| *read.lock().unwrap() = result,
+        );
+    }
+    let result = read.lock().unwrap().take().unwrap();
+    assert_eq!(result.count(), 1);
+    assert!(result.row_bytes(0) >= info.min_row_bytes());
+    assert_eq!(result.data(0).len(), result.row_bytes(0) * 8);
+    assert!(result.data(0)[..info.min_row_bytes()]
+        .iter()
+        .all(|b| *b >= 0xf0));
+}
+
+#[test]
+fn test_async_rescale_and_read_pixels_yuv420_is_unsupported_on_raster() {
+    use std::sync::{Arc, Mutex};
+
+    let mut surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+    surface.canvas().clear(crate::Color::WHITE);
+    let read = Arc::new(Mutex::new(None));
+    {
+        let read = read.clone();
+        surface.async_rescale_and_read_pixels_yuv420(
+            YUVColorSpace::JPEG,
+            None,
+            IRect::from_wh(16, 16),
+            (8, 8),
+            RescaleGamma::Src,
+            RescaleMode::Nearest,
+            move |result| *read.lock().unwrap() = Some(result.is_none()),
+        );
+    }
+    // Raster surfaces do not support YUV420 reads.
+    assert_eq!(*read.lock().unwrap(), Some(true));
+}
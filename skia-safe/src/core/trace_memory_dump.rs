@@ -1 +1,149 @@
-// TODO: wrap SkTraceMemoryDump
+use skia_bindings::RustTraceMemoryDump_Param;
+use std::{
+    ffi::CStr,
+    os::raw::{c_char, c_void},
+};
+
+pub use skia_bindings::SkTraceMemoryDump_LevelOfDetail as LevelOfDetail;
+#[test]
+fn test_level_of_detail_naming() {
+    let _ = LevelOfDetail::ObjectsBreakdowns;
+}
+
+/// Receives the memory statistics of Skia's caches, see
+/// [crate::graphics::dump_memory_statistics()].
+///
+/// Memory is reported as a tree of dumps, `dump_name` is a `/` separated path to the dump.
+pub trait TraceMemoryDump {
+    /// Reports a numeric `value` of the dump `dump_name`, for example its size in `"bytes"`.
+    fn dump_numeric_value(&mut self, dump_name: &str, value_name: &str, units: &str, value: u64);
+
+    /// Reports a string `value` of the dump `dump_name`.
+    fn dump_string_value(&mut self, _dump_name: &str, _value_name: &str, _value: &str) {}
+
+    /// Reports that the memory of the dump `dump_name` is backed by an object of another
+    /// memory allocator, for example a GPU resource.
+    fn set_memory_backing(&mut self, dump_name: &str, backing_type: &str, backing_object_id: &str);
+
+    /// The level of detail the memory statistics are requested in.
+    fn level_of_detail(&self) -> LevelOfDetail;
+
+    /// Whether objects that are wrapped by Skia, but were not created by it should be reported.
+    fn should_dump_wrapped_objects(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the parameters of a native trace memory dump that forwards to `dump`.
+pub(crate) fn native_trace_memory_dump<T: TraceMemoryDump>(
+    dump: &mut T,
+) -> RustTraceMemoryDump_Param {
+    RustTraceMemoryDump_Param {
+        data: dump as *mut T as *mut c_void,
+        dumpNumericValue: Some(dump_numeric_value::<T>),
+        dumpStringValue: Some(dump_string_value::<T>),
+        setMemoryBacking: Some(set_memory_backing::<T>),
+        getRequestedDetails: Some(get_requested_details::<T>),
+        shouldDumpWrappedObjects: Some(should_dump_wrapped_objects::<T>),
+    }
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> std::borrow::Cow<'a, str> {
+    if s.is_null() {
+        "".into()
+    } else {
+        CStr::from_ptr(s).to_string_lossy()
+    }
+}
+
+unsafe extern "C" fn dump_numeric_value<T: TraceMemoryDump>(
+    data: *mut c_void,
+    dump_name: *const c_char,
+    value_name: *const c_char,
+    units: *const c_char,
+    value: u64,
+) {
+    (*(data as *mut T)).dump_numeric_value(
+        &to_str(dump_name),
+        &to_str(value_name),
+        &to_str(units),
+        value,
+    )
+}
+
+unsafe extern "C" fn dump_string_value<T: TraceMemoryDump>(
+    data: *mut c_void,
+    dump_name: *const c_char,
+    value_name: *const c_char,
+    value: *const c_char,
+) {
+    (*(data as *mut T)).dump_string_value(&to_str(dump_name), &to_str(value_name), &to_str(value))
+}
+
+unsafe extern "C" fn set_memory_backing<T: TraceMemoryDump>(
+    data: *mut c_void,
+    dump_name: *const c_char,
+    backing_type: *const c_char,
+    backing_object_id: *const c_char,
+) {
+    (*(data as *mut T)).set_memory_backing(
+        &to_str(dump_name),
+        &to_str(backing_type),
+        &to_str(backing_object_id),
+    )
+}
+
+unsafe extern "C" fn get_requested_details<T: TraceMemoryDump>(data: *mut c_void) -> LevelOfDetail {
+    (*(data as *const T)).level_of_detail()
+}
+
+unsafe extern "C" fn should_dump_wrapped_objects<T: TraceMemoryDump>(data: *mut c_void) -> bool {
+    (*(data as *const T)).should_dump_wrapped_objects()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LevelOfDetail, TraceMemoryDump};
+    use crate::{graphics, Font, Typeface};
+
+    #[derive(Default)]
+    struct Collector {
+        values: Vec<(String, String, String, u64)>,
+    }
+
+    impl TraceMemoryDump for Collector {
+        fn dump_numeric_value(
+            &mut self,
+            dump_name: &str,
+            value_name: &str,
+            units: &str,
+            value: u64,
+        ) {
+            self.values
+                .push((dump_name.into(), value_name.into(), units.into(), value))
+        }
+
+        fn set_memory_backing(&mut self, _: &str, _: &str, _: &str) {}
+
+        fn level_of_detail(&self) -> LevelOfDetail {
+            LevelOfDetail::ObjectsBreakdowns
+        }
+    }
+
+    #[test]
+    fn dump_font_cache_statistics() {
+        // Make sure there is something in the font cache.
+        let font = Font::new(Typeface::default(), 12.0);
+        font.measure_str("Hello", None);
+
+        let mut collector = Collector::default();
+        graphics::dump_memory_statistics(&mut collector);
+        assert!(collector
+            .values
+            .iter()
+            .any(
+                |(dump_name, _, units, _)| dump_name.starts_with("skia/sk_glyph_cache")
+                    && units == "bytes"
+            ));
+    }
+}
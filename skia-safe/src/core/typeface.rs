@@ -3,10 +3,10 @@ use crate::{
     font_parameters::VariationAxis,
     interop::{self, MemoryStream, NativeStreamBase, StreamAsset},
     prelude::*,
-    Data, FontArguments, FontStyle, GlyphId, Rect, Unichar,
+    Data, FontArguments, FontMgr, FontStyle, GlyphId, Rect, Unichar,
 };
 use skia_bindings::{self as sb, SkRefCntBase, SkTypeface, SkTypeface_LocalizedStrings};
-use std::{ffi, fmt, ptr};
+use std::{ffi, fmt, io, ptr};
 
 pub type FontId = skia_bindings::SkFontID;
 pub type FontTableTag = skia_bindings::SkFontTableTag;
@@ -129,7 +129,27 @@ impl Typeface {
     // from_file is unsupported, because it is unclear what the
     // encoding of the path name is. from_data can be used instead.
 
-    // TODO: MakeFromStream()?
+    /// Creates a typeface from the font `index` read from `stream`, without reading the whole
+    /// font into memory, see [crate::FontMgr::new_from_stream()].
+    pub fn from_stream<R: io::Read + io::Seek + Send + 'static>(
+        stream: R,
+        index: impl Into<Option<usize>>,
+    ) -> Option<Typeface> {
+        let index = index.into().unwrap_or_default().try_into().ok()?;
+        let stream = StreamAsset::from_reader(stream);
+        Typeface::from_ptr(unsafe {
+            sb::C_SkTypeface_MakeFromStream(stream.into_stream_ptr(), index)
+        })
+    }
+
+    /// Creates a typeface from the font read from `stream` with `arguments`, using the default
+    /// font manager.
+    pub fn from_stream_with_arguments<R: io::Read + io::Seek + Send + 'static>(
+        stream: R,
+        arguments: &FontArguments,
+    ) -> Option<Typeface> {
+        FontMgr::default().new_from_stream_with_arguments(stream, arguments)
+    }
 
     pub fn from_data(data: impl Into<Data>, index: impl Into<Option<usize>>) -> Option<Typeface> {
         Typeface::from_ptr(unsafe {
@@ -309,6 +329,15 @@ impl Iterator for LocalizedStringsIter {
 mod tests {
     use super::{SerializeBehavior, Typeface};
 
+    #[test]
+    fn typeface_from_stream() {
+        let tf = Typeface::default();
+        let (data, index) = tf.to_font_data().unwrap();
+        let from_stream = Typeface::from_stream(std::io::Cursor::new(data), index).unwrap();
+        assert_eq!(from_stream.family_name(), tf.family_name());
+        assert_eq!(from_stream.count_glyphs(), tf.count_glyphs());
+    }
+
     #[test]
     fn serialize_and_deserialize_default_typeface() {
         let tf = Typeface::default();
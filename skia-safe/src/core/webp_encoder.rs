@@ -0,0 +1,48 @@
+use super::image_encoder::{encode_to_data, encode_to_writer};
+use crate::prelude::*;
+use crate::{Data, Pixmap};
+use skia_bindings as sb;
+use std::io;
+
+pub use sb::SkWebpEncoder_Compression as WebpCompression;
+
+#[test]
+fn test_webp_compression_naming() {
+    let _ = WebpCompression::Lossless;
+}
+
+/// Encodes pixmaps as WebP.
+///
+/// If the pixmap has a [crate::ColorSpace], its ICC profile is embedded.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WebpEncoder {
+    pub compression: WebpCompression,
+    /// For [WebpCompression::Lossy], the quality from `0.0` to `100.0`. For
+    /// [WebpCompression::Lossless], the effort spent on making the file smaller.
+    pub quality: f32,
+}
+
+impl Default for WebpEncoder {
+    fn default() -> Self {
+        WebpEncoder {
+            compression: WebpCompression::Lossy,
+            quality: 100.0,
+        }
+    }
+}
+
+impl WebpEncoder {
+    /// Encodes `src`, returns `None` if it can not be encoded.
+    pub fn encode(&self, src: &Pixmap) -> Option<Data> {
+        encode_to_data(|stream| self.encode_native(stream, src))
+    }
+
+    /// Encodes `src` and writes it to `writer`.
+    pub fn encode_to_writer(&self, src: &Pixmap, writer: &mut impl io::Write) -> io::Result<()> {
+        encode_to_writer(writer, |stream| self.encode_native(stream, src))
+    }
+
+    fn encode_native(&self, stream: &mut sb::SkWStream, src: &Pixmap) -> bool {
+        unsafe { sb::C_SkWebpEncoder_Encode(stream, src.native(), self.compression, self.quality) }
+    }
+}
@@ -1,13 +1,16 @@
 use crate::{
     interop::{self, AsStr},
     prelude::*,
-    ColorFilter, Data, Matrix, Shader,
+    Color4f, ColorFilter, Data, Matrix, Point, Shader, M44,
 };
 use skia_bindings::{
     self as sb, SkRefCntBase, SkRuntimeEffect, SkRuntimeEffect_Options, SkRuntimeEffect_Uniform,
     SkRuntimeEffect_Varying,
 };
-use std::{ffi::CStr, fmt};
+use std::{
+    ffi::{CStr, CString},
+    fmt,
+};
 
 pub type Uniform = Handle<SkRuntimeEffect_Uniform>;
 
@@ -125,23 +128,179 @@ pub struct Options {
 
 impl NativeTransmutable<SkRuntimeEffect_Options> for Options {}
 
-pub fn new(sksl: impl AsRef<str>) -> Result<RuntimeEffect, String> {
+pub fn new(sksl: impl AsRef<str>) -> Result<RuntimeEffect, CompileError> {
     new_with_options(sksl, None)
 }
 
 pub fn new_with_options<'a>(
     sksl: impl AsRef<str>,
     options: impl Into<Option<&'a Options>>,
-) -> Result<RuntimeEffect, String> {
+) -> Result<RuntimeEffect, CompileError> {
+    let sksl = sksl.as_ref();
     let str = interop::String::from_str(sksl);
     let options = options.into().copied().unwrap_or_default();
     let mut error = interop::String::default();
     RuntimeEffect::from_ptr(unsafe {
         sb::C_SkRuntimeEffect_Make(str.native(), options.native(), error.native_mut())
     })
-    .ok_or_else(|| error.as_str().to_owned())
+    .ok_or_else(|| CompileError::parse(error.as_str(), sksl))
+}
+
+/// The severity of a [Diagnostic].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single message of the SkSL compiler, see [CompileError::diagnostics()].
+///
+/// Diagnostics are parsed from the compiler's error text, which reports lines only, so
+/// [Diagnostic::column] is a best-effort guess.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The 1-based line in the SkSL source, `None` if the compiler did not report one.
+    pub line: Option<usize>,
+    /// The 1-based column in the line, best-effort.
+    ///
+    /// The compiler reports lines only, so the column is the first occurrence in the line of the
+    /// first token quoted in the message that is not part of a longer identifier, `None` if
+    /// there is none or it can not be found in the line. It may point to the wrong place if the
+    /// token appears more than once in the line, or if the message quotes a type name instead
+    /// of source text.
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Renders the line of `sksl` the diagnostic refers to, followed by a line with a caret
+    /// below the column, or that underlines the whole line if the column is not known.
+    ///
+    /// Returns `None` if the diagnostic does not refer to a line of `sksl`.
+    pub fn render_source_line(&self, sksl: &str) -> Option<String> {
+        let source_line = sksl.lines().nth(self.line?.checked_sub(1)?)?;
+        let indent = |n: usize| -> String {
+            source_line
+                .chars()
+                .take(n)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect()
+        };
+        let marker = match self.column {
+            Some(column) => format!("{}^", indent(column.saturating_sub(1))),
+            None => {
+                let trimmed = source_line.trim_start();
+                let start = source_line.chars().count() - trimmed.chars().count();
+                let width = trimmed.trim_end().chars().count().max(1);
+                format!("{}{}", indent(start), "^".repeat(width))
+            }
+        };
+        Some(format!("{}\n{}", source_line, marker))
+    }
+
+    fn parse(line: &str, sksl: &str) -> Option<Diagnostic> {
+        let (severity, rest) = if let Some(rest) = line.strip_prefix("error: ") {
+            (Severity::Error, rest)
+        } else if let Some(rest) = line.strip_prefix("warning: ") {
+            (Severity::Warning, rest)
+        } else {
+            return None;
+        };
+
+        let (line_number, message) = match rest.find(": ") {
+            Some(i) => match rest[..i].parse::<usize>() {
+                Ok(number) => (Some(number), &rest[i + 2..]),
+                Err(_) => (None, rest),
+            },
+            None => (None, rest),
+        };
+
+        let column = line_number
+            .and_then(|n| sksl.lines().nth(n.checked_sub(1)?))
+            .and_then(|source_line| {
+                let quoted = message.split('\'').nth(1)?;
+                if quoted.is_empty() {
+                    return None;
+                }
+                let byte_offset = find_token(source_line, quoted)?;
+                Some(source_line[..byte_offset].chars().count() + 1)
+            });
+
+        Some(Diagnostic {
+            severity,
+            line: line_number,
+            column,
+            message: message.to_owned(),
+        })
+    }
+}
+
+/// Returns the byte offset of the first occurrence of `token` in `line` that is not part of a
+/// longer identifier, so that `x` is not found in `max`.
+fn find_token(line: &str, token: &str) -> Option<usize> {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(token).map(|(i, _)| i).find(|&i| {
+        let before = line[..i].chars().next_back();
+        let after = line[i + token.len()..].chars().next();
+        !(token.starts_with(is_identifier) && before.map_or(false, is_identifier)
+            || token.ends_with(is_identifier) && after.map_or(false, is_identifier))
+    })
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "{}: {}:{}: {}", severity, line, column, self.message)
+            }
+            (Some(line), None) => write!(f, "{}: {}: {}", severity, line, self.message),
+            _ => write!(f, "{}: {}", severity, self.message),
+        }
+    }
 }
 
+/// The error returned if a [RuntimeEffect] can not be compiled from its SkSL source.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompileError {
+    text: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl CompileError {
+    fn parse(text: &str, sksl: &str) -> CompileError {
+        CompileError {
+            text: text.to_owned(),
+            diagnostics: text
+                .lines()
+                .filter_map(|line| Diagnostic::parse(line, sksl))
+                .collect(),
+        }
+    }
+
+    /// The diagnostics of the compiler, in the order they were reported.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// The error text as reported by the compiler.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text.trim_end())
+    }
+}
+
+impl std::error::Error for CompileError {}
+
 impl fmt::Debug for RuntimeEffect {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("RuntimeEffect")
@@ -275,14 +434,472 @@ impl RuntimeEffect {
     }
 }
 
-// TODO: wrap SkRuntimeEffectBuilder, SkRuntimeShaderBuilder
+/// The components of a value that is assigned to a uniform, see [UniformValue].
+#[derive(Clone, PartialEq, Debug)]
+pub enum UniformComponents {
+    Floats(Vec<f32>),
+    Ints(Vec<i32>),
+}
+
+impl UniformComponents {
+    fn describe(&self) -> String {
+        match self {
+            UniformComponents::Floats(floats) => plural(floats.len(), "float"),
+            UniformComponents::Ints(ints) => plural(ints.len(), "int"),
+        }
+    }
+}
+
+/// A value that can be assigned to a uniform by [BuilderUniform::set()].
+pub trait UniformValue {
+    fn components(&self) -> UniformComponents;
+}
+
+impl UniformValue for f32 {
+    fn components(&self) -> UniformComponents {
+        UniformComponents::Floats(vec![*self])
+    }
+}
+
+impl UniformValue for i32 {
+    fn components(&self) -> UniformComponents {
+        UniformComponents::Ints(vec![*self])
+    }
+}
+
+impl UniformValue for [f32] {
+    fn components(&self) -> UniformComponents {
+        UniformComponents::Floats(self.to_vec())
+    }
+}
+
+impl UniformValue for [i32] {
+    fn components(&self) -> UniformComponents {
+        UniformComponents::Ints(self.to_vec())
+    }
+}
+
+macro_rules! uniform_value_arrays {
+    ($($n:literal),*) => {
+        $(
+            impl UniformValue for [f32; $n] {
+                fn components(&self) -> UniformComponents {
+                    UniformComponents::Floats(self.to_vec())
+                }
+            }
+
+            impl UniformValue for [i32; $n] {
+                fn components(&self) -> UniformComponents {
+                    UniformComponents::Ints(self.to_vec())
+                }
+            }
+        )*
+    };
+}
+
+uniform_value_arrays!(2, 3, 4, 9, 16);
+
+impl UniformValue for Point {
+    fn components(&self) -> UniformComponents {
+        UniformComponents::Floats(vec![self.x, self.y])
+    }
+}
+
+impl UniformValue for Color4f {
+    fn components(&self) -> UniformComponents {
+        UniformComponents::Floats(vec![self.r, self.g, self.b, self.a])
+    }
+}
+
+/// A [Matrix] is assigned to a `float3x3` uniform in column-major order.
+impl UniformValue for Matrix {
+    fn components(&self) -> UniformComponents {
+        UniformComponents::Floats(
+            (0..3)
+                .flat_map(|c| (0..3).map(move |r| self[r * 3 + c]))
+                .collect(),
+        )
+    }
+}
+
+/// A [M44] is assigned to a `float4x4` uniform in column-major order.
+impl UniformValue for M44 {
+    fn components(&self) -> UniformComponents {
+        let mut v = [0.0; 16];
+        self.get_col_major(&mut v);
+        UniformComponents::Floats(v.to_vec())
+    }
+}
+
+impl<T: UniformValue + ?Sized> UniformValue for &T {
+    fn components(&self) -> UniformComponents {
+        (**self).components()
+    }
+}
+
+/// Errors of a [RuntimeShaderBuilder].
+#[derive(Clone, PartialEq, Debug)]
+pub enum BuilderError {
+    /// The effect does not declare a uniform with this name.
+    UnknownUniform(String),
+    /// The value assigned to a uniform does not match its type or array size.
+    UniformMismatch {
+        name: String,
+        expected: String,
+        provided: String,
+    },
+    /// The effect does not declare a child with this name.
+    UnknownChild(String),
+    /// No shader was assigned to this child.
+    MissingChild(String),
+    /// The effect could not create a shader, for example because it is a color filter.
+    ShaderCreationFailed,
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::UnknownUniform(name) => write!(f, "unknown uniform `{}`", name),
+            BuilderError::UniformMismatch {
+                name,
+                expected,
+                provided,
+            } => write!(
+                f,
+                "uniform `{}` expects {}, got {}",
+                name, expected, provided
+            ),
+            BuilderError::UnknownChild(name) => write!(f, "unknown child `{}`", name),
+            BuilderError::MissingChild(name) => write!(f, "no shader assigned to child `{}`", name),
+            BuilderError::ShaderCreationFailed => write!(f, "failed to create the shader"),
+        }
+    }
+}
+
+impl std::error::Error for BuilderError {}
+
+/// Builds a [Shader] from a [RuntimeEffect] by assigning its uniforms and children by name.
+///
+/// Values are checked against the type and the array size of the uniform they are assigned to.
+#[derive(Clone, Debug)]
+pub struct RuntimeShaderBuilder {
+    effect: RuntimeEffect,
+    uniforms: Vec<u8>,
+    children: Vec<Option<Shader>>,
+}
+
+impl RuntimeShaderBuilder {
+    pub fn new(effect: RuntimeEffect) -> Self {
+        let uniforms = vec![0; effect.uniform_size()];
+        let children = vec![None; effect.children().count()];
+        RuntimeShaderBuilder {
+            effect,
+            uniforms,
+            children,
+        }
+    }
+
+    pub fn effect(&self) -> &RuntimeEffect {
+        &self.effect
+    }
+
+    /// The uniform data assigned so far.
+    pub fn uniforms(&self) -> &[u8] {
+        &self.uniforms
+    }
+
+    /// Returns the uniform `name`, to assign a value to.
+    pub fn uniform<'a>(&'a mut self, name: &'a str) -> BuilderUniform<'a> {
+        BuilderUniform { owner: self, name }
+    }
+
+    /// Returns the child `name`, to assign a shader to.
+    pub fn child<'a>(&'a mut self, name: &'a str) -> BuilderChild<'a> {
+        BuilderChild { owner: self, name }
+    }
+
+    /// Creates a shader from the uniforms and children assigned.
+    ///
+    /// Returns an error if a child was not assigned, or the effect can not create a shader.
+    pub fn make_shader<'a>(
+        &self,
+        local_matrix: impl Into<Option<&'a Matrix>>,
+        is_opaque: bool,
+    ) -> Result<Shader, BuilderError> {
+        let children = self
+            .children
+            .iter()
+            .zip(self.effect.children())
+            .map(|(child, name)| {
+                child
+                    .clone()
+                    .ok_or_else(|| BuilderError::MissingChild(name.to_owned()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.effect
+            .make_shader(
+                Data::new_copy(&self.uniforms),
+                children,
+                local_matrix,
+                is_opaque,
+            )
+            .ok_or(BuilderError::ShaderCreationFailed)
+    }
+}
+
+/// A uniform of a [RuntimeShaderBuilder], see [RuntimeShaderBuilder::uniform()].
+#[derive(Debug)]
+pub struct BuilderUniform<'a> {
+    owner: &'a mut RuntimeShaderBuilder,
+    name: &'a str,
+}
+
+impl BuilderUniform<'_> {
+    /// Assigns `value` to the uniform.
+    ///
+    /// Returns an error if the effect does not declare the uniform, or `value` does not match
+    /// the type or the array size of the uniform.
+    pub fn set(self, value: impl UniformValue) -> Result<(), BuilderError> {
+        let name = CString::new(self.name)
+            .map_err(|_| BuilderError::UnknownUniform(self.name.to_owned()))?;
+        let uniform = self
+            .owner
+            .effect
+            .find_uniform(&name)
+            .ok_or_else(|| BuilderError::UnknownUniform(self.name.to_owned()))?;
+
+        let (is_float, components) = uniform_layout(uniform.ty());
+        let count: usize = uniform.count().try_into().unwrap();
+        let expected = if is_float {
+            plural(components * count, "float")
+        } else {
+            plural(components * count, "int")
+        };
+        let mismatch = |provided: &UniformComponents| BuilderError::UniformMismatch {
+            name: self.name.to_owned(),
+            expected: format!("{} ({})", expected, type_name(uniform)),
+            provided: provided.describe(),
+        };
+
+        let value = value.components();
+        let bytes: Vec<u8> = match &value {
+            UniformComponents::Floats(floats) if is_float && floats.len() == components * count => {
+                floats
+                    .iter()
+                    .flat_map(|f| f.to_ne_bytes().to_vec())
+                    .collect()
+            }
+            UniformComponents::Ints(ints) if !is_float && ints.len() == components * count => {
+                ints.iter().flat_map(|i| i.to_ne_bytes().to_vec()).collect()
+            }
+            _ => return Err(mismatch(&value)),
+        };
+        debug_assert_eq!(bytes.len(), uniform.size_in_bytes());
+        let offset = uniform.offset();
+        self.owner.uniforms[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        Ok(())
+    }
+}
+
+/// A child of a [RuntimeShaderBuilder], see [RuntimeShaderBuilder::child()].
+#[derive(Debug)]
+pub struct BuilderChild<'a> {
+    owner: &'a mut RuntimeShaderBuilder,
+    name: &'a str,
+}
+
+impl BuilderChild<'_> {
+    /// Assigns `shader` to the child, or removes the shader assigned.
+    ///
+    /// Returns an error if the effect does not declare the child.
+    pub fn set(self, shader: impl Into<Option<Shader>>) -> Result<(), BuilderError> {
+        let index = CString::new(self.name)
+            .ok()
+            .and_then(|name| self.owner.effect.find_child(&name))
+            .ok_or_else(|| BuilderError::UnknownChild(self.name.to_owned()))?;
+        self.owner.children[index] = shader.into();
+        Ok(())
+    }
+}
+
+/// Returns whether the components of uniforms of `ty` are floats, and the number of components.
+fn uniform_layout(ty: uniform::Type) -> (bool, usize) {
+    use uniform::Type::*;
+    match ty {
+        Float => (true, 1),
+        Float2 => (true, 2),
+        Float3 => (true, 3),
+        Float4 => (true, 4),
+        Float2x2 => (true, 4),
+        Float3x3 => (true, 9),
+        Float4x4 => (true, 16),
+        Int => (false, 1),
+        Int2 => (false, 2),
+        Int3 => (false, 3),
+        Int4 => (false, 4),
+    }
+}
+
+fn type_name(uniform: &Uniform) -> String {
+    use uniform::Type::*;
+    let ty = match uniform.ty() {
+        Float => "float",
+        Float2 => "float2",
+        Float3 => "float3",
+        Float4 => "float4",
+        Float2x2 => "float2x2",
+        Float3x3 => "float3x3",
+        Float4x4 => "float4x4",
+        Int => "int",
+        Int2 => "int2",
+        Int3 => "int3",
+        Int4 => "int4",
+    };
+    if uniform.is_array() {
+        format!("{}[{}]", ty, uniform.count())
+    } else {
+        ty.to_owned()
+    }
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        format!("1 {}", word)
+    } else {
+        format!("{} {}s", n, word)
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::NativeTransmutable;
+    use super::{BuilderError, CompileError, Diagnostic, RuntimeShaderBuilder, Severity};
+    use crate::{prelude::NativeTransmutable, shaders, Color, Color4f, Matrix};
+    use std::ffi::CString;
 
     #[test]
     fn options_layout() {
         super::Options::test_layout()
     }
+
+    #[test]
+    fn compile_error_diagnostics() {
+        let sksl = "half4 main(float2 p) {\n    return half4(undefined_color);\n}\n";
+        let error = super::new(sksl).unwrap_err();
+        let diagnostics = error.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.line, Some(2));
+        assert_eq!(diagnostic.column, Some(18));
+        assert_eq!(
+            diagnostic.render_source_line(sksl).unwrap(),
+            "    return half4(undefined_color);\n                 ^"
+        );
+    }
+
+    #[test]
+    fn parse_compile_error_text() {
+        let sksl = "uniform float a;\nhalf4 main() { return b; }";
+        let error = CompileError::parse(
+            "error: 2: unknown identifier 'b'\nwarning: 1: unused uniform\nerror: no main\n2 errors\n",
+            sksl,
+        );
+        let diagnostics = error.diagnostics();
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "error: 2:23: unknown identifier 'b'"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[1].render_source_line(sksl).unwrap(),
+            "uniform float a;\n^^^^^^^^^^^^^^^^"
+        );
+        assert_eq!(diagnostics[2].line, None);
+        assert_eq!(diagnostics[2].render_source_line(sksl), None);
+
+        let sksl = "half4 main() { return half4(max(x, 1)); }";
+        let error = CompileError::parse("error: 1: unknown identifier 'x'\n", sksl);
+        let diagnostic = &error.diagnostics()[0];
+        assert_eq!(diagnostic.column, Some(33));
+        assert_eq!(
+            diagnostic.render_source_line(sksl).unwrap(),
+            format!("{}\n{}^", sksl, " ".repeat(32))
+        );
+
+        let diagnostic = Diagnostic {
+            column: Some(0),
+            ..diagnostic.clone()
+        };
+        assert_eq!(
+            diagnostic.render_source_line(sksl).unwrap(),
+            format!("{}\n^", sksl)
+        );
+    }
+
+    const SKSL: &str = r#"
+        uniform float u_time;
+        uniform float4 u_color;
+        uniform float3x3 u_matrix;
+        uniform int2 u_offset;
+        uniform shader u_child;
+
+        half4 main(float2 p) {
+            return half4(u_color) * half(u_time) + sample(u_child, p);
+        }
+    "#;
+
+    #[test]
+    fn builder_sets_uniforms_and_children() {
+        let effect = super::new(SKSL).unwrap();
+        let mut builder = RuntimeShaderBuilder::new(effect);
+        builder.uniform("u_time").set(1.5f32).unwrap();
+        builder
+            .uniform("u_color")
+            .set([1.0f32, 0.0, 0.0, 1.0])
+            .unwrap();
+        builder.uniform("u_matrix").set(Matrix::default()).unwrap();
+        builder.uniform("u_offset").set([1i32, 2]).unwrap();
+
+        let offset = builder
+            .effect()
+            .find_uniform(CString::new("u_time").unwrap())
+            .unwrap()
+            .offset();
+        assert_eq!(
+            &builder.uniforms()[offset..offset + 4],
+            &1.5f32.to_ne_bytes()
+        );
+
+        assert_eq!(
+            builder.make_shader(None, false).unwrap_err(),
+            BuilderError::MissingChild("u_child".into())
+        );
+        builder
+            .child("u_child")
+            .set(shaders::color(Color::BLUE))
+            .unwrap();
+        assert!(builder.make_shader(None, false).is_ok());
+    }
+
+    #[test]
+    fn builder_reports_mismatches() {
+        let effect = super::new(SKSL).unwrap();
+        let mut builder = RuntimeShaderBuilder::new(effect);
+        assert_eq!(
+            builder.uniform("u_unknown").set(1.0f32),
+            Err(BuilderError::UnknownUniform("u_unknown".into()))
+        );
+        let err = builder.uniform("u_color").set(1.0f32).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "uniform `u_color` expects 4 floats (float4), got 1 float"
+        );
+        assert!(builder.uniform("u_color").set(Color4f::default()).is_ok());
+        assert!(builder.uniform("u_offset").set([1.0f32, 2.0]).is_err());
+        assert_eq!(
+            builder.child("u_unknown").set(None),
+            Err(BuilderError::UnknownChild("u_unknown".into()))
+        );
+    }
 }
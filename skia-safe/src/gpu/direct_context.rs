@@ -8,7 +8,7 @@ use super::{
     BackendFormat, BackendRenderTarget, BackendSurfaceMutableState, BackendTexture, ContextOptions,
     FlushInfo, RecordingContext, SemaphoresSubmitted,
 };
-use crate::{image, prelude::*, Data};
+use crate::{image, native_trace_memory_dump, prelude::*, Data, TraceMemoryDump};
 use skia_bindings::{self as sb, GrDirectContext, GrDirectContext_DirectContextID, SkRefCntBase};
 use std::{
     fmt,
@@ -278,7 +278,11 @@ impl DirectContext {
         unsafe { self.native_mut().checkAsyncWorkCompletion() }
     }
 
-    // TODO: dumpMemoryStatistics()
+    /// Reports the memory statistics of the GPU resources of this context to `dump`.
+    pub fn dump_memory_statistics(&self, dump: &mut impl TraceMemoryDump) {
+        let param = native_trace_memory_dump(dump);
+        unsafe { sb::C_GrDirectContext_dumpMemoryStatistics(self.native(), &param) }
+    }
 
     pub fn supports_distance_field_text(&self) -> bool {
         unsafe { self.native().supportsDistanceFieldText() }
@@ -6,8 +6,11 @@
 use crate::prelude::*;
 use crate::Data;
 use skia_bindings as sb;
-use skia_bindings::{SkDynamicMemoryWStream, SkMemoryStream, SkStream, SkStreamAsset, SkWStream};
-use std::ptr;
+use skia_bindings::{
+    RustStream as NativeRustStream, RustWStream as NativeRustWStream, SkDynamicMemoryWStream,
+    SkMemoryStream, SkStream, SkStreamAsset, SkWStream,
+};
+use std::{ffi, io, mem, os::raw, ptr, slice};
 use std::{fmt, marker::PhantomData};
 
 /// Trait representing an Skia allocated Stream type with a base class of SkStream.
@@ -57,6 +60,36 @@ impl fmt::Debug for StreamAsset {
     }
 }
 
+impl StreamAsset {
+    /// Creates a stream asset that reads from `reader`.
+    ///
+    /// Duplicates of the stream share `reader` but have their own position, so `reader` is
+    /// seeked before every read. `reader` is dropped with the last stream that shares it.
+    pub fn from_reader<R: io::Read + io::Seek + Send + 'static>(reader: R) -> StreamAsset {
+        StreamAsset::from_ptr(unsafe {
+            sb::C_RustStreamAsset_New(
+                Box::into_raw(Box::new(reader)) as *mut ffi::c_void,
+                Some(read_trampoline::<R>),
+                Some(seek_trampoline::<R>),
+                Some(drop_trampoline::<R>),
+            )
+        })
+        .unwrap()
+    }
+
+    /// Returns a new stream that reads the same content from its start.
+    pub fn duplicate(&self) -> Option<StreamAsset> {
+        StreamAsset::from_ptr(unsafe { sb::C_SkStreamAsset_duplicate(self.native()) })
+    }
+
+    /// Releases the stream, for passing it to functions that take ownership.
+    pub(crate) fn into_stream_ptr(mut self) -> *mut SkStreamAsset {
+        let stream: *mut SkStreamAsset = self.native_mut();
+        mem::forget(self);
+        stream
+    }
+}
+
 #[repr(C)]
 pub struct MemoryStream<'a> {
     native: ptr::NonNull<SkMemoryStream>,
@@ -157,6 +190,253 @@ impl DynamicMemoryWStream {
     }
 }
 
+/// A native `SkStream` that reads from a borrowed Rust [io::Read] implementation.
+pub struct RustStream<'a> {
+    native: Handle<NativeRustStream>,
+    pd: PhantomData<&'a mut ()>,
+}
+
+impl NativeBase<SkStream> for NativeRustStream {}
+
+impl NativeDrop for NativeRustStream {
+    fn drop(&mut self) {
+        unsafe { sb::C_RustStream_destruct(self) }
+    }
+}
+
+impl NativeStreamBase for NativeRustStream {
+    fn as_stream_mut(&mut self) -> &mut SkStream {
+        self.base_mut()
+    }
+}
+
+impl NativeAccess<NativeRustStream> for RustStream<'_> {
+    fn native(&self) -> &NativeRustStream {
+        self.native.native()
+    }
+    fn native_mut(&mut self) -> &mut NativeRustStream {
+        self.native.native_mut()
+    }
+}
+
+impl fmt::Debug for RustStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RustStream").finish()
+    }
+}
+
+impl<'a> RustStream<'a> {
+    pub fn new<R: io::Read>(reader: &'a mut R) -> Self {
+        let native = Handle::construct(|stream| unsafe {
+            sb::C_RustStream_construct(
+                stream,
+                reader as *mut R as *mut ffi::c_void,
+                Some(read_trampoline::<R>),
+            )
+        });
+        RustStream {
+            native,
+            pd: PhantomData,
+        }
+    }
+
+    pub fn stream_mut(&mut self) -> &mut SkStream {
+        self.native_mut().as_stream_mut()
+    }
+}
+
+/// A heap allocated native `SkStream` that owns a Rust [io::Read] implementation.
+///
+/// Used for Skia functions that take ownership of the stream they read from. Unlike [Stream],
+/// it is not [Send], because the reader may not be.
+pub struct OwnedRustStream(ptr::NonNull<NativeRustStream>);
+
+impl Drop for OwnedRustStream {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkStream_delete(self.native_mut().as_stream_mut()) }
+    }
+}
+
+impl NativeAccess<NativeRustStream> for OwnedRustStream {
+    fn native(&self) -> &NativeRustStream {
+        unsafe { self.0.as_ref() }
+    }
+    fn native_mut(&mut self) -> &mut NativeRustStream {
+        unsafe { self.0.as_mut() }
+    }
+}
+
+impl fmt::Debug for OwnedRustStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedRustStream").finish()
+    }
+}
+
+impl OwnedRustStream {
+    pub fn new<R: io::Read + 'static>(reader: R) -> Self {
+        Self::new_native(reader, None)
+    }
+
+    /// Creates a stream that supports rewinding, seeking, and querying its length.
+    pub fn new_seekable<R: io::Read + io::Seek + 'static>(reader: R) -> Self {
+        Self::new_native(reader, Some(seek_trampoline::<R>))
+    }
+
+    fn new_native<R: io::Read + 'static>(reader: R, seek: sb::RustStreamSeek) -> Self {
+        let ptr = unsafe {
+            sb::C_RustStream_New(
+                Box::into_raw(Box::new(reader)) as *mut ffi::c_void,
+                Some(read_trampoline::<R>),
+                seek,
+                Some(drop_trampoline::<R>),
+            )
+        };
+        OwnedRustStream(ptr::NonNull::new(ptr).unwrap())
+    }
+
+    /// Releases the stream, for passing it to functions that take ownership.
+    pub(crate) fn into_stream_ptr(mut self) -> *mut SkStream {
+        let stream: *mut SkStream = self.native_mut().as_stream_mut();
+        mem::forget(self);
+        stream
+    }
+}
+
+/// A native `SkWStream` that writes to a borrowed Rust [io::Write] implementation.
+pub struct RustWStream<'a> {
+    native: Handle<NativeRustWStream>,
+    // Dropped after the native stream, which may still write to it.
+    state: Box<WriterState<'a>>,
+}
+
+/// The writer of a [RustWStream] and the first error it returned.
+struct WriterState<'a> {
+    writer: &'a mut dyn io::Write,
+    error: Option<io::Error>,
+}
+
+impl NativeBase<SkWStream> for NativeRustWStream {}
+
+impl NativeDrop for NativeRustWStream {
+    fn drop(&mut self) {
+        unsafe { sb::C_RustWStream_destruct(self) }
+    }
+}
+
+impl NativeAccess<NativeRustWStream> for RustWStream<'_> {
+    fn native(&self) -> &NativeRustWStream {
+        self.native.native()
+    }
+    fn native_mut(&mut self) -> &mut NativeRustWStream {
+        self.native.native_mut()
+    }
+}
+
+impl fmt::Debug for RustWStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RustWStream").finish()
+    }
+}
+
+impl<'a> RustWStream<'a> {
+    pub fn new<W: io::Write>(writer: &'a mut W) -> Self {
+        let mut state = Box::new(WriterState {
+            writer,
+            error: None,
+        });
+        let native = Handle::construct(|stream| unsafe {
+            sb::C_RustWStream_construct(
+                stream,
+                &mut *state as *mut WriterState as *mut ffi::c_void,
+                Some(write_trampoline),
+                Some(flush_trampoline),
+            )
+        });
+        RustWStream { native, state }
+    }
+
+    pub fn stream_mut(&mut self) -> &mut SkWStream {
+        self.native_mut().base_mut()
+    }
+
+    /// Returns the first error the writer returned, if any.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.state.error.take()
+    }
+}
+
+unsafe extern "C" fn write_trampoline(
+    data: *mut ffi::c_void,
+    buffer: *const ffi::c_void,
+    count: usize,
+) -> bool {
+    let state = &mut *(data as *mut WriterState);
+    let buffer = slice::from_raw_parts(buffer as *const u8, count);
+    match state.writer.write_all(buffer) {
+        Ok(()) => true,
+        Err(e) => {
+            state.error.get_or_insert(e);
+            false
+        }
+    }
+}
+
+unsafe extern "C" fn flush_trampoline(data: *mut ffi::c_void) {
+    let state = &mut *(data as *mut WriterState);
+    // `SkWStream::flush()` can not report errors, so they are kept for the caller.
+    if let Err(e) = state.writer.flush() {
+        state.error.get_or_insert(e);
+    }
+}
+
+unsafe extern "C" fn drop_trampoline<R>(data: *mut ffi::c_void) {
+    drop(Box::from_raw(data as *mut R))
+}
+
+unsafe extern "C" fn seek_trampoline<R: io::Seek>(
+    data: *mut ffi::c_void,
+    offset: i64,
+    whence: raw::c_int,
+) -> i64 {
+    let reader = &mut *(data as *mut R);
+    let pos = match whence {
+        0 => io::SeekFrom::Start(offset.max(0) as u64),
+        1 => io::SeekFrom::Current(offset),
+        _ => io::SeekFrom::End(offset),
+    };
+    reader
+        .seek(pos)
+        .map(|position| position as i64)
+        .unwrap_or(-1)
+}
+
+unsafe extern "C" fn read_trampoline<R: io::Read>(
+    data: *mut ffi::c_void,
+    buffer: *mut ffi::c_void,
+    count: usize,
+) -> usize {
+    let reader = &mut *(data as *mut R);
+    if buffer.is_null() {
+        // Skia requests to skip `count` bytes.
+        return io::copy(&mut reader.take(count as u64), &mut io::sink())
+            .map(|skipped| skipped as usize)
+            .unwrap_or(0);
+    }
+    let buffer = slice::from_raw_parts_mut(buffer as *mut u8, count);
+    // `io::Read::read()` may return less bytes than requested before the end of the stream is
+    // reached, but `SkStream::read()` must not.
+    let mut bytes_read = 0;
+    while bytes_read < count {
+        match reader.read(&mut buffer[bytes_read..]) {
+            Ok(0) => break,
+            Ok(n) => bytes_read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(_) => break,
+        }
+    }
+    bytes_read
+}
+
 #[test]
 fn detaching_empty_dynamic_memory_w_stream_leads_to_non_null_data() {
     let mut stream = DynamicMemoryWStream::new();
@@ -169,3 +449,109 @@ fn memory_stream_from_bytes() {
     let stream = MemoryStream::from_bytes(&[1, 2, 3]);
     drop(stream);
 }
+
+#[test]
+fn rust_stream_reads_and_skips() {
+    let bytes = [1u8, 2, 3, 4, 5];
+    let mut reader = &bytes[..];
+    let mut stream = RustStream::new(&mut reader);
+    let mut buffer = [0u8; 2];
+    let read = unsafe { sb::C_SkStream_read(stream.stream_mut(), buffer.as_mut_ptr() as _, 2) };
+    assert_eq!(read, 2);
+    assert_eq!(buffer, [1, 2]);
+    let skipped = unsafe { sb::C_SkStream_read(stream.stream_mut(), ptr::null_mut(), 2) };
+    assert_eq!(skipped, 2);
+    let read = unsafe { sb::C_SkStream_read(stream.stream_mut(), buffer.as_mut_ptr() as _, 2) };
+    assert_eq!(read, 1);
+    assert_eq!(buffer[0], 5);
+}
+
+#[test]
+fn rust_stream_seeks() {
+    let mut stream = OwnedRustStream::new_seekable(io::Cursor::new(vec![1u8, 2, 3, 4, 5]));
+    let stream = stream.native_mut().as_stream_mut();
+    let mut buffer = [0u8; 2];
+    unsafe {
+        assert!(sb::C_SkStream_hasLength(stream));
+        assert_eq!(sb::C_SkStream_getLength(stream), 5);
+        assert!(sb::C_SkStream_seek(stream, 3));
+        assert_eq!(sb::C_SkStream_read(stream, buffer.as_mut_ptr() as _, 2), 2);
+        assert_eq!(buffer, [4, 5]);
+        assert!(sb::C_SkStream_rewind(stream));
+        assert_eq!(sb::C_SkStream_getPosition(stream), 0);
+    }
+}
+
+#[test]
+fn stream_asset_duplicates_have_their_own_position() {
+    let mut stream = StreamAsset::from_reader(io::Cursor::new(vec![1u8, 2, 3, 4, 5]));
+    let mut buffer = [0u8; 2];
+    unsafe {
+        let native = stream.native_mut().as_stream_mut();
+        assert_eq!(sb::C_SkStream_getLength(native), 5);
+        assert!(sb::C_SkStream_seek(native, 3));
+    }
+    let mut duplicate = stream.duplicate().unwrap();
+    unsafe {
+        let native = stream.native_mut().as_stream_mut();
+        assert_eq!(sb::C_SkStream_read(native, buffer.as_mut_ptr() as _, 2), 2);
+        assert_eq!(buffer, [4, 5]);
+        let duplicate = duplicate.native_mut().as_stream_mut();
+        assert_eq!(sb::C_SkStream_getPosition(duplicate), 0);
+        assert_eq!(
+            sb::C_SkStream_read(duplicate, buffer.as_mut_ptr() as _, 2),
+            2
+        );
+        assert_eq!(buffer, [1, 2]);
+    }
+}
+
+#[test]
+fn rust_w_stream_writes() {
+    let mut bytes = Vec::new();
+    {
+        let mut stream = RustWStream::new(&mut bytes);
+        assert!(unsafe {
+            sb::C_SkWStream_write(stream.stream_mut(), [1u8, 2, 3].as_ptr() as _, 3)
+        });
+    }
+    assert_eq!(bytes, [1, 2, 3]);
+}
+
+#[test]
+fn rust_w_stream_keeps_the_first_write_error() {
+    struct Failing;
+    impl io::Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = Failing;
+    let mut stream = RustWStream::new(&mut writer);
+    assert!(!unsafe { sb::C_SkWStream_write(stream.stream_mut(), [1u8].as_ptr() as _, 1) });
+    assert_eq!(
+        stream.take_error().unwrap().kind(),
+        io::ErrorKind::BrokenPipe
+    );
+    assert!(stream.take_error().is_none());
+}
+
+#[test]
+fn owned_rust_stream_drops_reader() {
+    use std::rc::Rc;
+    let bytes = Rc::new(());
+    struct Reader(Rc<()>);
+    impl io::Read for Reader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Ok(0)
+        }
+    }
+    let stream = OwnedRustStream::new(Reader(bytes.clone()));
+    assert_eq!(Rc::strong_count(&bytes), 2);
+    drop(stream);
+    assert_eq!(Rc::strong_count(&bytes), 1);
+}
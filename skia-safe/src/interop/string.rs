@@ -101,6 +101,17 @@ impl FromStrs for Vec<String> {
     }
 }
 
+/// Converts a nul terminated string that is passed from Skia to a Rust callback.
+///
+/// Null pointers and strings that are not valid UTF-8 are converted to an empty string.
+#[cfg(any(feature = "svg", feature = "animation", feature = "particles"))]
+pub(crate) unsafe fn callback_str<'a>(str: *const std::os::raw::c_char) -> &'a str {
+    if str.is_null() {
+        return "";
+    }
+    std::ffi::CStr::from_ptr(str).to_str().unwrap_or_default()
+}
+
 #[test]
 fn string_from_rust_and_back() {
     let str = "Hello";
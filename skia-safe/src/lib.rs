@@ -3,7 +3,7 @@
 pub mod codec;
 #[deprecated(since = "0.33.1", note = "use codec::Result")]
 pub use codec::Result as CodecResult;
-pub use codec::{Codec, EncodedOrigin};
+pub use codec::{AnimatedImage, Codec, EncodedOrigin};
 
 mod core;
 mod docs;
@@ -1,7 +1,13 @@
 #[cfg(feature = "textlayout")]
 pub(crate) mod paragraph;
+#[cfg(feature = "particles")]
+pub mod particles;
+#[cfg(any(feature = "svg", feature = "animation", feature = "particles"))]
+pub mod resources;
 #[cfg(feature = "textlayout")]
 pub mod shaper;
+#[cfg(feature = "animation")]
+pub mod skottie;
 #[cfg(feature = "textlayout")]
 pub use shaper::{icu, Shaper};
 
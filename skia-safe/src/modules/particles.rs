@@ -0,0 +1,191 @@
+//! Skia's particle system.
+//!
+//! Effects are described in JSON and loaded into [EffectParams]. Each [Effect] created from the
+//! parameters runs an independent instance of the effect.
+
+use crate::prelude::*;
+use crate::resources::NativeResourceProvider;
+use crate::{Canvas, Point};
+use skia_bindings as sb;
+use skia_bindings::{SkParticleEffect, SkParticleEffectParams, SkRefCntBase};
+use std::{ffi, fmt, slice};
+
+/// The parameters of a particle effect, loaded from its JSON description.
+pub type EffectParams = RCHandle<SkParticleEffectParams>;
+
+impl NativeRefCountedBase for SkParticleEffectParams {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for EffectParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EffectParams").finish()
+    }
+}
+
+impl EffectParams {
+    /// Loads and compiles the effect described by `json`.
+    ///
+    /// Returns `None` if `json` is not a JSON object.
+    pub fn from_json(json: &str) -> Option<EffectParams> {
+        Self::from_json_with_resources(json, None)
+    }
+
+    /// Loads and compiles the effect described by `json`, images that are referred to by the
+    /// effect's drawable are loaded with the `resource_provider`.
+    ///
+    /// Returns `None` if `json` is not a JSON object.
+    pub fn from_json_with_resources(
+        json: &str,
+        resource_provider: impl Into<Option<NativeResourceProvider>>,
+    ) -> Option<EffectParams> {
+        let resource_provider = resource_provider.into();
+        EffectParams::from_ptr(unsafe {
+            sb::C_SkParticleEffectParams_MakeFromJSON(
+                json.as_ptr() as _,
+                json.len(),
+                resource_provider.as_ref().native_ptr_or_null(),
+            )
+        })
+    }
+}
+
+/// A running instance of a particle effect.
+pub type Effect = RCHandle<SkParticleEffect>;
+
+impl NativeRefCountedBase for SkParticleEffect {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Effect")
+            .field("is_alive", &self.is_alive())
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
+impl Effect {
+    pub fn new(params: &EffectParams) -> Effect {
+        Effect::from_ptr(unsafe { sb::C_SkParticleEffect_Make(params.clone().into_ptr()) }).unwrap()
+    }
+
+    /// Starts the effect at the time `now` in seconds, positioned at `position`.
+    ///
+    /// A `looping` effect restarts when its lifetime ends.
+    pub fn start(&mut self, now: f64, looping: bool, position: impl Into<Point>) -> &mut Self {
+        unsafe {
+            sb::C_SkParticleEffect_start(self.native_mut(), now, looping, position.into().native())
+        }
+        self
+    }
+
+    /// Advances the effect and all its particles to the time `now` in seconds.
+    pub fn update(&mut self, now: f64) -> &mut Self {
+        unsafe { sb::C_SkParticleEffect_update(self.native_mut(), now) }
+        self
+    }
+
+    pub fn draw(&mut self, canvas: &mut Canvas) {
+        unsafe { sb::C_SkParticleEffect_draw(self.native_mut(), canvas.native_mut()) }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        unsafe { sb::C_SkParticleEffect_isAlive(self.native()) }
+    }
+
+    /// The number of live particles.
+    pub fn count(&self) -> usize {
+        unsafe { sb::C_SkParticleEffect_getCount(self.native()) }
+            .try_into()
+            .unwrap()
+    }
+
+    pub fn set_position(&mut self, position: impl Into<Point>) -> &mut Self {
+        unsafe { sb::C_SkParticleEffect_setPosition(self.native_mut(), position.into().native()) }
+        self
+    }
+
+    /// The names of the uniforms that are declared by the effect's code.
+    pub fn uniform_names(&self) -> Vec<String> {
+        let count = unsafe { sb::C_SkParticleEffect_uniformCount(self.native()) };
+        (0..count)
+            .map(|i| {
+                let name = unsafe { sb::C_SkParticleEffect_uniformName(self.native(), i) };
+                unsafe { ffi::CStr::from_ptr(name) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    /// The values of the uniform `name`, or `None` if the effect does not declare it.
+    pub fn uniform(&self, name: &str) -> Option<&[f32]> {
+        let name = ffi::CString::new(name).ok()?;
+        let mut count = 0;
+        let ptr = unsafe {
+            sb::C_SkParticleEffect_uniform(self.native_mut_force(), name.as_ptr(), &mut count)
+        };
+        (!ptr.is_null()).if_true_then_some(|| unsafe { slice::from_raw_parts(ptr, count) })
+    }
+
+    /// The mutable values of the uniform `name`, or `None` if the effect does not declare it.
+    pub fn uniform_mut(&mut self, name: &str) -> Option<&mut [f32]> {
+        let name = ffi::CString::new(name).ok()?;
+        let mut count = 0;
+        let ptr =
+            unsafe { sb::C_SkParticleEffect_uniform(self.native_mut(), name.as_ptr(), &mut count) };
+        (!ptr.is_null()).if_true_then_some(|| unsafe { slice::from_raw_parts_mut(ptr, count) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Effect, EffectParams};
+    use crate::Surface;
+
+    const EFFECT: &str = r#"{
+        "MaxCount": 32,
+        "Drawable": { "Type": "SkCircleDrawable", "Radius": 2 },
+        "Code": [
+            "uniform float speed;",
+            "void effectSpawn(inout Effect effect) {",
+            "  effect.rate = 10;",
+            "}",
+            "void spawn(inout Particle p) {",
+            "  p.lifetime = 1;",
+            "  p.vel.y = speed;",
+            "}"
+        ],
+        "Bindings": []
+    }"#;
+
+    #[test]
+    fn load_start_and_draw() {
+        let params = EffectParams::from_json(EFFECT).unwrap();
+        let mut effect = Effect::new(&params);
+        effect.start(0.0, true, (10, 10));
+        assert!(effect.is_alive());
+        effect.update(0.5);
+
+        let mut surface = Surface::new_raster_n32_premul((20, 20)).unwrap();
+        effect.draw(surface.canvas());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn set_uniform() {
+        let params = EffectParams::from_json(EFFECT).unwrap();
+        let mut effect = Effect::new(&params);
+        assert!(effect.uniform_names().iter().any(|n| n == "speed"));
+        effect.uniform_mut("speed").unwrap()[0] = 5.0;
+        assert_eq!(effect.uniform("speed").unwrap(), &[5.0]);
+        assert!(effect.uniform("unknown").is_none());
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(EffectParams::from_json("[]").is_none());
+    }
+}
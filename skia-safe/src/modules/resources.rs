@@ -0,0 +1,81 @@
+//! Resource providers for the modules that load external resources, like images and fonts.
+
+use crate::interop;
+use crate::prelude::*;
+use crate::Data;
+use skia_bindings as sb;
+use skia_bindings::{
+    skresources_ResourceProvider, RustResourceProvider_Param, SkData, SkRefCntBase,
+};
+use std::{ffi, fmt, os::raw::c_char};
+
+/// Loads external resources that are referenced by documents, for example images referred to
+/// from SVG files.
+///
+/// Image resources are loaded with [ResourceProvider::load()] and decoded by Skia.
+pub trait ResourceProvider {
+    /// Loads a generic resource specified by `resource_path` and `resource_name`.
+    fn load(&self, resource_path: &str, resource_name: &str) -> Option<Data>;
+
+    /// Loads a font specified by its `name` and `url`.
+    fn load_font(&self, _name: &str, _url: &str) -> Option<Data> {
+        None
+    }
+}
+
+/// A reference counted native resource provider that forwards to a Rust [ResourceProvider].
+pub type NativeResourceProvider = RCHandle<skresources_ResourceProvider>;
+
+impl NativeRefCountedBase for skresources_ResourceProvider {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for NativeResourceProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeResourceProvider").finish()
+    }
+}
+
+impl NativeResourceProvider {
+    /// Creates a native resource provider that forwards to `provider`.
+    ///
+    /// Skia may call `provider` from other threads, so it must be [Send] and [Sync].
+    pub fn new<T: ResourceProvider + Send + Sync + 'static>(provider: T) -> Self {
+        let param = RustResourceProvider_Param {
+            data: Box::into_raw(Box::new(provider)) as *mut ffi::c_void,
+            drop: Some(drop_provider::<T>),
+            load: Some(load::<T>),
+            loadFont: Some(load_font::<T>),
+        };
+        Self::from_ptr(unsafe { sb::C_RustResourceProvider_New(&param) }).unwrap()
+    }
+}
+
+unsafe extern "C" fn drop_provider<T: ResourceProvider>(data: *mut ffi::c_void) {
+    drop(Box::from_raw(data as *mut T))
+}
+
+unsafe extern "C" fn load<T: ResourceProvider>(
+    data: *mut ffi::c_void,
+    resource_path: *const c_char,
+    resource_name: *const c_char,
+) -> *mut SkData {
+    let provider = &*(data as *const T);
+    provider
+        .load(
+            interop::callback_str(resource_path),
+            interop::callback_str(resource_name),
+        )
+        .into_ptr_or_null()
+}
+
+unsafe extern "C" fn load_font<T: ResourceProvider>(
+    data: *mut ffi::c_void,
+    name: *const c_char,
+    url: *const c_char,
+) -> *mut SkData {
+    let provider = &*(data as *const T);
+    provider
+        .load_font(interop::callback_str(name), interop::callback_str(url))
+        .into_ptr_or_null()
+}
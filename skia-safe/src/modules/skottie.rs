@@ -0,0 +1,7 @@
+//! Bindings for Skottie, Skia's player of [Lottie](https://airbnb.io/lottie/) animations.
+
+mod animation;
+pub use animation::*;
+
+mod property;
+pub use property::*;
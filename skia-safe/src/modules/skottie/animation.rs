@@ -0,0 +1,290 @@
+use super::{NativePropertyObserver, PropertyObserver};
+use crate::interop::{self, MemoryStream, NativeStreamBase, RustStream};
+use crate::prelude::*;
+use crate::resources::NativeResourceProvider;
+use crate::{Canvas, Data, FontMgr, Rect, Size};
+use skia_bindings as sb;
+use skia_bindings::{skottie_Animation, SkStream};
+use std::{fmt, io};
+
+/// A loaded Lottie animation.
+pub type Animation = RCHandle<skottie_Animation>;
+
+impl NativeRefCounted for skottie_Animation {
+    fn _ref(&self) {
+        unsafe { sb::C_skottie_Animation_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_skottie_Animation_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_skottie_Animation_unique(self) }
+    }
+}
+
+bitflags! {
+    #[derive(Default)]
+    pub struct RenderFlags: u32 {
+        /// When rendering into a known transparent buffer, clients can pass this flag to avoid
+        /// some unnecessary compositing overhead for animations using layer blend modes.
+        const SKIP_TOP_LEVEL_ISOLATION = 0x01;
+        /// By default, content is clipped to the intrinsic animation bounds (as determined by
+        /// its size). If this flag is set, then the animation can draw outside of the bounds.
+        const DISABLE_TOP_LEVEL_CLIPPING = 0x02;
+    }
+}
+
+impl fmt::Debug for Animation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Animation")
+            .field("version", &self.version())
+            .field("duration", &self.duration())
+            .field("fps", &self.fps())
+            .field("in_point", &self.in_point())
+            .field("out_point", &self.out_point())
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+/// Optional components that are used while loading an [Animation].
+#[derive(Default)]
+pub struct Builder {
+    font_mgr: Option<FontMgr>,
+    resource_provider: Option<NativeResourceProvider>,
+    property_observer: Option<NativePropertyObserver>,
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("font_mgr", &self.font_mgr)
+            .field("resource_provider", &self.resource_provider)
+            .field("property_observer", &self.property_observer)
+            .finish()
+    }
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the font manager that is used to resolve the fonts of text layers.
+    pub fn set_font_mgr(mut self, font_mgr: impl Into<Option<FontMgr>>) -> Self {
+        self.font_mgr = font_mgr.into();
+        self
+    }
+
+    /// Sets the provider that loads external images and fonts.
+    pub fn set_resource_provider(
+        mut self,
+        resource_provider: impl Into<Option<NativeResourceProvider>>,
+    ) -> Self {
+        self.resource_provider = resource_provider.into();
+        self
+    }
+
+    /// Sets an observer that receives handles to the properties of the animation while it
+    /// loads.
+    pub fn set_property_observer(
+        mut self,
+        observer: impl PropertyObserver + Send + 'static,
+    ) -> Self {
+        self.property_observer = Some(NativePropertyObserver::new(observer));
+        self
+    }
+
+    pub fn make_from_data(&self, data: &Data) -> Option<Animation> {
+        let mut stream = MemoryStream::from_bytes(data.as_bytes());
+        self.make_from_native_stream(stream.native_mut().as_stream_mut())
+    }
+
+    pub fn make_from_bytes(&self, json: &[u8]) -> Option<Animation> {
+        let mut stream = MemoryStream::from_bytes(json);
+        self.make_from_native_stream(stream.native_mut().as_stream_mut())
+    }
+
+    pub fn make_from_stream(&self, json: &mut impl io::Read) -> Option<Animation> {
+        let mut stream = RustStream::new(json);
+        self.make_from_native_stream(stream.stream_mut())
+    }
+
+    fn make_from_native_stream(&self, stream: &mut SkStream) -> Option<Animation> {
+        Animation::from_ptr(unsafe {
+            sb::C_skottie_Animation_MakeFromStream(
+                stream,
+                self.font_mgr.clone().into_ptr_or_null(),
+                self.resource_provider.clone().into_ptr_or_null(),
+                self.property_observer.clone().into_ptr_or_null(),
+            )
+        })
+    }
+}
+
+impl Animation {
+    pub fn from_data(data: &Data) -> Option<Animation> {
+        Builder::default().make_from_data(data)
+    }
+
+    pub fn from_bytes(json: &[u8]) -> Option<Animation> {
+        Builder::default().make_from_bytes(json)
+    }
+
+    pub fn from_stream(json: &mut impl io::Read) -> Option<Animation> {
+        Builder::default().make_from_stream(json)
+    }
+
+    /// Draws the current animation frame.
+    ///
+    /// If `dst` is `None`, the animation is rendered at its intrinsic size.
+    pub fn render<'a>(&self, canvas: &mut Canvas, dst: impl Into<Option<&'a Rect>>) {
+        self.render_with_flags(canvas, dst, RenderFlags::default())
+    }
+
+    pub fn render_with_flags<'a>(
+        &self,
+        canvas: &mut Canvas,
+        dst: impl Into<Option<&'a Rect>>,
+        flags: RenderFlags,
+    ) {
+        unsafe {
+            sb::C_skottie_Animation_render(
+                self.native(),
+                canvas.native_mut(),
+                dst.into().native_ptr_or_null(),
+                flags.bits(),
+            )
+        }
+    }
+
+    /// Updates the animation state for frame `t`, in frame units, relative to
+    /// [Animation::in_point()].
+    ///
+    /// Fractional values are allowed and meaningful, for example `0.0` is the first frame,
+    /// `1.0` the second frame, and `0.5` halfway between them.
+    pub fn seek_frame(&mut self, t: f64) -> &mut Self {
+        unsafe { sb::C_skottie_Animation_seekFrame(self.native_mut(), t) }
+        self
+    }
+
+    /// Updates the animation state for time `t`, in seconds, relative to
+    /// [Animation::in_point()].
+    pub fn seek_frame_time(&mut self, t: f64) -> &mut Self {
+        unsafe { sb::C_skottie_Animation_seekFrameTime(self.native_mut(), t) }
+        self
+    }
+
+    /// The duration of the animation in seconds.
+    pub fn duration(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_duration(self.native()) }
+    }
+
+    /// The frame rate of the animation in frames per second.
+    pub fn fps(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_fps(self.native()) }
+    }
+
+    /// The first frame of the animation, in frame units.
+    pub fn in_point(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_inPoint(self.native()) }
+    }
+
+    /// The frame after the last frame of the animation, in frame units.
+    pub fn out_point(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_outPoint(self.native()) }
+    }
+
+    /// The intrinsic size of the animation.
+    pub fn size(&self) -> Size {
+        let mut size = Size::default();
+        unsafe { sb::C_skottie_Animation_size(self.native(), size.native_mut()) };
+        size
+    }
+
+    /// The Lottie format version of the animation.
+    pub fn version(&self) -> String {
+        let mut version = interop::String::default();
+        unsafe { sb::C_skottie_Animation_version(self.native(), version.native_mut()) };
+        version.as_str().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animation, Builder, RenderFlags};
+    use crate::skottie::{ColorPropertyHandle, PropertyObserver};
+    use crate::{Color, Rect, Size, Surface};
+    use std::sync::{Arc, Mutex};
+
+    const ANIMATION: &str = r#"{
+        "v": "5.5.2", "fr": 30, "ip": 0, "op": 60, "w": 20, "h": 10,
+        "layers": [{
+            "ty": 4, "nm": "Layer", "ind": 1, "ip": 0, "op": 60, "st": 0,
+            "ks": {},
+            "shapes": [
+                { "ty": "rc", "nm": "Rect", "p": { "a": 0, "k": [10, 5] },
+                  "s": { "a": 0, "k": [20, 10] }, "r": { "a": 0, "k": 0 } },
+                { "ty": "fl", "nm": "Fill", "c": { "a": 0, "k": [1, 0, 0, 1] },
+                  "o": { "a": 0, "k": 100 } }
+            ]
+        }]
+    }"#;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn load_and_query() {
+        let animation = Animation::from_bytes(ANIMATION.as_bytes()).unwrap();
+        assert_eq!(animation.version(), "5.5.2");
+        assert_eq!(animation.fps(), 30.0);
+        assert_eq!(animation.duration(), 2.0);
+        assert_eq!(animation.in_point(), 0.0);
+        assert_eq!(animation.out_point(), 60.0);
+        assert_eq!(animation.size(), Size::new(20.0, 10.0));
+    }
+
+    #[test]
+    fn seek_and_render() {
+        let mut animation = Animation::from_bytes(ANIMATION.as_bytes()).unwrap();
+        animation.seek_frame(30.0).seek_frame_time(1.5);
+
+        let mut surface = Surface::new_raster_n32_premul((40, 20)).unwrap();
+        animation.render(surface.canvas(), &Rect::from_wh(40.0, 20.0));
+        animation.render_with_flags(
+            surface.canvas(),
+            None,
+            RenderFlags::DISABLE_TOP_LEVEL_CLIPPING,
+        );
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((30, 15)), Color::RED);
+    }
+
+    #[test]
+    fn observe_and_change_color() {
+        struct Observer(Arc<Mutex<Vec<ColorPropertyHandle>>>);
+
+        impl PropertyObserver for Observer {
+            fn on_color_property(&mut self, _node_name: &str, property: ColorPropertyHandle) {
+                self.0.lock().unwrap().push(property);
+            }
+        }
+
+        let colors = Arc::new(Mutex::new(Vec::new()));
+        let animation = Builder::new()
+            .set_property_observer(Observer(colors.clone()))
+            .make_from_bytes(ANIMATION.as_bytes())
+            .unwrap();
+
+        let mut colors = colors.lock().unwrap();
+        let color = colors.first_mut().unwrap();
+        assert_eq!(color.get(), Color::RED);
+        color.set(Color::BLUE);
+
+        let mut surface = Surface::new_raster_n32_premul((20, 10)).unwrap();
+        animation.render(surface.canvas(), None);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((10, 5)), Color::BLUE);
+    }
+}
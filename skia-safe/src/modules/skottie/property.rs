@@ -0,0 +1,133 @@
+use crate::interop;
+use crate::prelude::*;
+use crate::Color;
+use skia_bindings as sb;
+use skia_bindings::{
+    skottie_ColorPropertyHandle, skottie_OpacityPropertyHandle, skottie_PropertyObserver,
+    RustPropertyObserver_Param, SkRefCntBase,
+};
+use std::{ffi, fmt, os::raw::c_char};
+
+/// A handle to a color property of an animation.
+///
+/// Changes made through the handle are visible the next time the animation is rendered.
+pub type ColorPropertyHandle = RefHandle<skottie_ColorPropertyHandle>;
+// The handle owns a reference to the node of the property, so that observers can move it out of
+// the loading thread.
+unsafe impl Send for ColorPropertyHandle {}
+
+impl NativeDrop for skottie_ColorPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_ColorPropertyHandle_delete(self) }
+    }
+}
+
+impl fmt::Debug for ColorPropertyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ColorPropertyHandle")
+            .field(&self.get())
+            .finish()
+    }
+}
+
+impl ColorPropertyHandle {
+    pub fn get(&self) -> Color {
+        Color::from_native_c(unsafe { sb::C_skottie_ColorPropertyHandle_get(self.native()) })
+    }
+
+    pub fn set(&mut self, color: impl Into<Color>) -> &mut Self {
+        unsafe {
+            sb::C_skottie_ColorPropertyHandle_set(self.native_mut(), color.into().into_native())
+        }
+        self
+    }
+}
+
+/// A handle to an opacity property of an animation, in the range `0.0` to `100.0`.
+///
+/// Changes made through the handle are visible the next time the animation is rendered.
+pub type OpacityPropertyHandle = RefHandle<skottie_OpacityPropertyHandle>;
+unsafe impl Send for OpacityPropertyHandle {}
+
+impl NativeDrop for skottie_OpacityPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_delete(self) }
+    }
+}
+
+impl fmt::Debug for OpacityPropertyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OpacityPropertyHandle")
+            .field(&self.get())
+            .finish()
+    }
+}
+
+impl OpacityPropertyHandle {
+    pub fn get(&self) -> f32 {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_get(self.native()) }
+    }
+
+    pub fn set(&mut self, opacity: f32) -> &mut Self {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_set(self.native_mut(), opacity) }
+        self
+    }
+}
+
+/// Receives handles to the animatable properties of an animation while it is being loaded.
+///
+/// `node_name` is the name of the node the property belongs to. The handles can be retained
+/// and used to modify the animation after it is loaded.
+pub trait PropertyObserver {
+    fn on_color_property(&mut self, _node_name: &str, _property: ColorPropertyHandle) {}
+    fn on_opacity_property(&mut self, _node_name: &str, _property: OpacityPropertyHandle) {}
+}
+
+/// A reference counted native property observer that forwards to a Rust [PropertyObserver].
+pub type NativePropertyObserver = RCHandle<skottie_PropertyObserver>;
+
+impl NativeRefCountedBase for skottie_PropertyObserver {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for NativePropertyObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativePropertyObserver").finish()
+    }
+}
+
+impl NativePropertyObserver {
+    pub fn new<T: PropertyObserver + Send + 'static>(observer: T) -> Self {
+        let param = RustPropertyObserver_Param {
+            data: Box::into_raw(Box::new(observer)) as *mut ffi::c_void,
+            drop: Some(drop_observer::<T>),
+            onColorProperty: Some(on_color_property::<T>),
+            onOpacityProperty: Some(on_opacity_property::<T>),
+        };
+        Self::from_ptr(unsafe { sb::C_RustPropertyObserver_New(&param) }).unwrap()
+    }
+}
+
+unsafe extern "C" fn drop_observer<T: PropertyObserver>(data: *mut ffi::c_void) {
+    drop(Box::from_raw(data as *mut T))
+}
+
+unsafe extern "C" fn on_color_property<T: PropertyObserver>(
+    data: *mut ffi::c_void,
+    node_name: *const c_char,
+    handle: *mut skottie_ColorPropertyHandle,
+) {
+    if let Some(handle) = ColorPropertyHandle::from_ptr(handle) {
+        (*(data as *mut T)).on_color_property(interop::callback_str(node_name), handle)
+    }
+}
+
+unsafe extern "C" fn on_opacity_property<T: PropertyObserver>(
+    data: *mut ffi::c_void,
+    node_name: *const c_char,
+    handle: *mut skottie_OpacityPropertyHandle,
+) {
+    if let Some(handle) = OpacityPropertyHandle::from_ptr(handle) {
+        (*(data as *mut T)).on_opacity_property(interop::callback_str(node_name), handle)
+    }
+}
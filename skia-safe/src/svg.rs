@@ -1,2 +1,7 @@
 pub mod canvas;
 pub use self::canvas::Canvas;
+
+#[cfg(feature = "svg")]
+mod dom;
+#[cfg(feature = "svg")]
+pub use self::dom::*;
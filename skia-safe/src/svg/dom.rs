@@ -0,0 +1,119 @@
+use crate::interop::{MemoryStream, NativeStreamBase, RustStream};
+use crate::prelude::*;
+use crate::resources::NativeResourceProvider;
+use crate::{Canvas, FontMgr, Size};
+use skia_bindings as sb;
+use skia_bindings::{SkRefCntBase, SkSVGDOM, SkStream};
+use std::{fmt, io};
+
+/// A parsed SVG document that can be rendered to a [Canvas].
+pub type Dom = RCHandle<SkSVGDOM>;
+
+impl NativeRefCountedBase for SkSVGDOM {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for Dom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dom")
+            .field("container_size", &self.container_size())
+            .finish()
+    }
+}
+
+impl Dom {
+    /// Parses an SVG document from `svg`.
+    pub fn from_bytes(svg: &[u8]) -> Option<Dom> {
+        let mut stream = MemoryStream::from_bytes(svg);
+        Self::from_native_stream(stream.native_mut().as_stream_mut(), None, None)
+    }
+
+    /// Parses an SVG document by reading it from `svg`.
+    pub fn from_stream(svg: &mut impl io::Read) -> Option<Dom> {
+        Self::from_stream_with_resources(svg, None, None)
+    }
+
+    /// Parses an SVG document by reading it from `svg`.
+    ///
+    /// `font_mgr` is used to resolve the fonts of text elements, if `None`, the default font
+    /// manager is used. Images referred to by the document are loaded with the
+    /// `resource_provider`.
+    pub fn from_stream_with_resources(
+        svg: &mut impl io::Read,
+        font_mgr: impl Into<Option<FontMgr>>,
+        resource_provider: impl Into<Option<NativeResourceProvider>>,
+    ) -> Option<Dom> {
+        let mut stream = RustStream::new(svg);
+        Self::from_native_stream(
+            stream.stream_mut(),
+            font_mgr.into(),
+            resource_provider.into(),
+        )
+    }
+
+    fn from_native_stream(
+        stream: &mut SkStream,
+        font_mgr: Option<FontMgr>,
+        resource_provider: Option<NativeResourceProvider>,
+    ) -> Option<Dom> {
+        Dom::from_ptr(unsafe {
+            sb::C_SkSVGDOM_MakeFromStream(
+                stream,
+                font_mgr.into_ptr_or_null(),
+                resource_provider.into_ptr_or_null(),
+            )
+        })
+    }
+
+    /// The size of the viewport the document is rendered into.
+    pub fn container_size(&self) -> Size {
+        let mut size = Size::default();
+        unsafe { sb::C_SkSVGDOM_containerSize(self.native(), size.native_mut()) };
+        size
+    }
+
+    pub fn set_container_size(&mut self, size: impl Into<Size>) -> &mut Self {
+        unsafe { sb::C_SkSVGDOM_setContainerSize(self.native_mut(), size.into().native()) };
+        self
+    }
+
+    /// Renders the document to the `canvas`.
+    pub fn render(&self, canvas: &mut Canvas) {
+        unsafe { sb::C_SkSVGDOM_render(self.native(), canvas.native_mut()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dom;
+    use crate::{Color, Size, Surface};
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+        <rect width="20" height="10" fill="red"/>
+    </svg>"#;
+
+    #[test]
+    fn load_and_render() {
+        let dom = Dom::from_bytes(SVG.as_bytes()).unwrap();
+        assert_eq!(dom.container_size(), Size::new(20.0, 10.0));
+
+        let mut surface = Surface::new_raster_n32_premul((20, 10)).unwrap();
+        dom.render(surface.canvas());
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((10, 5)), Color::RED);
+    }
+
+    #[test]
+    fn set_container_size() {
+        let mut dom = Dom::from_bytes(SVG.as_bytes()).unwrap();
+        dom.set_container_size((40, 20));
+        assert_eq!(dom.container_size(), Size::new(40.0, 20.0));
+    }
+
+    #[test]
+    fn load_from_stream() {
+        let mut reader = SVG.as_bytes();
+        let dom = Dom::from_stream(&mut reader).unwrap();
+        assert_eq!(dom.container_size(), Size::new(20.0, 10.0));
+    }
+}
@@ -13,3 +13,4 @@ pub use null_canvas::*;
 pub mod parse_path;
 pub mod shadow_utils;
 pub mod text_utils;
+pub mod tile_pyramid;
@@ -0,0 +1,644 @@
+//! Builds tile pyramids for deep zoom viewers from huge images and pictures.
+//!
+//! The source is decoded or rendered one band of rows at a time. Every level of the pyramid is
+//! derived from the band above it by averaging 2x2 pixels, so only the rows that are needed for
+//! the next row of tiles are kept in memory at each level.
+
+use crate::codec::{self, ScanlineOrder, ZeroInitialized};
+use crate::{Canvas, Codec, Color, EncodedImageFormat, IRect, ISize, ImageInfo, Picture, Pixmap};
+use std::io;
+
+const BYTES_PER_PIXEL: usize = 4;
+
+/// The tile layout of a pyramid.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Layout {
+    /// Deep Zoom Image (DZI). Level `0` is a single pixel, the last level has the size of the
+    /// source. Tiles include `overlap` pixels of their neighbors and tiles at the right and bottom
+    /// edges are smaller than the tile size. Tile paths are `{level}/{column}_{row}.{extension}`.
+    DeepZoom,
+    /// Slippy map tiles. Level `0` is a single tile that covers the whole source. All tiles
+    /// have the tile size and are padded with transparent pixels at the right and bottom edges.
+    /// Tile paths are `{level}/{column}/{row}.{extension}`, row `0` is at the top.
+    XYZ,
+    /// Like [Layout::XYZ], but row `0` is at the bottom, as specified by the Tile Map Service.
+    TMS,
+}
+
+/// A tile produced by a [TilePyramid].
+#[derive(Debug)]
+pub struct Tile<'a> {
+    pub level: usize,
+    pub column: usize,
+    /// The row of the tile, counted from the bottom for [Layout::TMS].
+    pub row: usize,
+    /// The area of the level that is covered by the tile, including its overlap.
+    pub bounds: IRect,
+    /// The path of the tile relative to the root of the tile set.
+    pub path: String,
+    /// The encoded tile.
+    pub data: &'a [u8],
+}
+
+/// Configures and builds tile pyramids.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TilePyramid {
+    layout: Layout,
+    tile_size: usize,
+    overlap: usize,
+    format: EncodedImageFormat,
+    quality: usize,
+}
+
+impl Default for TilePyramid {
+    fn default() -> Self {
+        TilePyramid {
+            layout: Layout::DeepZoom,
+            tile_size: 254,
+            overlap: 1,
+            format: EncodedImageFormat::JPEG,
+            quality: 90,
+        }
+    }
+}
+
+impl TilePyramid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub fn set_layout(&mut self, layout: Layout) -> &mut Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn tile_size(&self) -> usize {
+        self.tile_size
+    }
+
+    pub fn set_tile_size(&mut self, tile_size: usize) -> &mut Self {
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// The number of pixels tiles share with their neighbors, only used by [Layout::DeepZoom].
+    pub fn overlap(&self) -> usize {
+        match self.layout {
+            Layout::DeepZoom => self.overlap,
+            Layout::XYZ | Layout::TMS => 0,
+        }
+    }
+
+    pub fn set_overlap(&mut self, overlap: usize) -> &mut Self {
+        self.overlap = overlap;
+        self
+    }
+
+    pub fn format(&self) -> EncodedImageFormat {
+        self.format
+    }
+
+    /// Sets the format of the tiles, [EncodedImageFormat::JPEG], [EncodedImageFormat::PNG],
+    /// and [EncodedImageFormat::WEBP] are supported.
+    pub fn set_format(&mut self, format: EncodedImageFormat) -> &mut Self {
+        self.format = format;
+        self
+    }
+
+    pub fn quality(&self) -> usize {
+        self.quality
+    }
+
+    /// Sets the encoder quality from `0` to `100`.
+    pub fn set_quality(&mut self, quality: usize) -> &mut Self {
+        self.quality = quality;
+        self
+    }
+
+    /// The file extension of the tiles, `None` if the format is not supported.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self.format {
+            EncodedImageFormat::JPEG => Some("jpg"),
+            EncodedImageFormat::PNG => Some("png"),
+            EncodedImageFormat::WEBP => Some("webp"),
+            _ => None,
+        }
+    }
+
+    /// The sizes of the levels of a pyramid for a source of `dimensions`, starting with level
+    /// `0`.
+    pub fn levels(&self, dimensions: impl Into<ISize>) -> Vec<ISize> {
+        let dimensions = dimensions.into();
+        if dimensions.is_empty() || self.tile_size == 0 {
+            return Vec::new();
+        }
+        let max_dimension = dimensions.width.max(dimensions.height) as usize;
+        let smallest_level = match self.layout {
+            Layout::DeepZoom => 1,
+            Layout::XYZ | Layout::TMS => self.tile_size,
+        };
+        let mut top_level = 0;
+        while smallest_level << top_level < max_dimension {
+            top_level += 1;
+        }
+        (0..=top_level)
+            .map(|level| {
+                let scale = 1 << (top_level - level);
+                ISize::new(
+                    div_ceil(dimensions.width as usize, scale) as i32,
+                    div_ceil(dimensions.height as usize, scale) as i32,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the `.dzi` descriptor of a [Layout::DeepZoom] pyramid for a source of
+    /// `dimensions`.
+    pub fn dzi_descriptor(&self, dimensions: impl Into<ISize>) -> String {
+        let dimensions = dimensions.into();
+        format!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                "\n",
+                r#"<Image xmlns="http://schemas.microsoft.com/deepzoom/2008" Format="{}" Overlap="{}" TileSize="{}">"#,
+                r#"<Size Width="{}" Height="{}"/></Image>"#,
+                "\n"
+            ),
+            self.extension().unwrap_or_default(),
+            self.overlap(),
+            self.tile_size,
+            dimensions.width,
+            dimensions.height
+        )
+    }
+
+    /// Builds the pyramid of the image decoded by `codec` and passes every tile to `on_tile`.
+    ///
+    /// The image is decoded scanline by scanline. Codecs that do not support top-down scanline
+    /// decoding decode every band of rows as a subset, see [codec::Options::subset].
+    ///
+    /// Returns an error if the codec supports neither of them, or if it can not decode subsets
+    /// that start at the first row of every band. WebP subsets must start at even rows, for
+    /// example, so WebP images require an even tile size. The complete image is never decoded
+    /// at once.
+    pub fn build_from_codec(
+        &self,
+        codec: &mut Codec,
+        mut on_tile: impl FnMut(&Tile) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let dimensions = codec.dimensions();
+        let info = ImageInfo::new_n32_premul(dimensions, None);
+        let row_bytes = info.min_row_bytes();
+
+        if codec.start_scanline_decode(&info, None) == codec::Result::Success
+            && codec.scanline_order() == ScanlineOrder::TopDown
+        {
+            // Rows that can not be decoded because of incomplete input are filled by the codec.
+            return self.build(
+                dimensions,
+                |band, _, rows| {
+                    codec.get_scanlines(band, rows, row_bytes);
+                    Ok(())
+                },
+                &mut on_tile,
+            );
+        }
+
+        // Codecs may move the top of a subset, which would decode the wrong rows.
+        let height = dimensions.height as usize;
+        if self.tile_size > 0 {
+            for y in (0..height).step_by(self.tile_size) {
+                let rows = self.tile_size.min(height - y);
+                let band = IRect::from_xywh(0, y as i32, dimensions.width, rows as i32);
+                match codec.valid_subset(band) {
+                    Some(valid) if valid == band => {}
+                    Some(_) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!(
+                                "the codec can not decode a subset that starts at row {}, \
+                                 the tile size must be aligned to the subsets it supports",
+                                y
+                            ),
+                        ))
+                    }
+                    None => return Err(no_band_decoding()),
+                }
+            }
+        }
+
+        // Decode band by band, so that the complete image is never held in memory.
+        self.build(
+            dimensions,
+            |band, y, rows| {
+                let band_info = ImageInfo::new_n32_premul((dimensions.width, rows as i32), None);
+                let options = codec::Options {
+                    zero_initialized: ZeroInitialized::No,
+                    subset: Some(IRect::from_xywh(0, y as i32, dimensions.width, rows as i32)),
+                    frame_index: 0,
+                    prior_frame: 0,
+                };
+                match codec.get_pixels_with_options(&band_info, band, row_bytes, Some(&options)) {
+                    codec::Result::Success | codec::Result::IncompleteInput => Ok(()),
+                    codec::Result::Unimplemented => Err(no_band_decoding()),
+                    result => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        codec::result_to_string(result),
+                    )),
+                }
+            },
+            &mut on_tile,
+        )
+    }
+
+    /// Builds the pyramid of the area `(0, 0, size.width, size.height)` of `picture` and passes
+    /// every tile to `on_tile`.
+    pub fn build_from_picture(
+        &self,
+        picture: &Picture,
+        size: impl Into<ISize>,
+        mut on_tile: impl FnMut(&Tile) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let size = size.into();
+        self.build(
+            size,
+            |band, y, rows| {
+                let info = ImageInfo::new_n32_premul((size.width, rows as i32), None);
+                let mut canvas = Canvas::from_raster_direct(&info, band, None, None)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "failed to render"))?;
+                canvas.clear(Color::TRANSPARENT);
+                canvas.translate((0.0, -(y as f32)));
+                canvas.draw_picture(picture, None, None);
+                Ok(())
+            },
+            &mut on_tile,
+        )
+    }
+
+    /// Reads the source into bands of rows with `read_band(band, first_row, rows)` and pushes
+    /// them through the levels of the pyramid.
+    fn build(
+        &self,
+        dimensions: ISize,
+        mut read_band: impl FnMut(&mut [u8], usize, usize) -> io::Result<()>,
+        on_tile: &mut dyn FnMut(&Tile) -> io::Result<()>,
+    ) -> io::Result<()> {
+        if self.tile_size == 0 || dimensions.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "tile size and dimensions must not be empty",
+            ));
+        }
+        if self.extension().is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unsupported tile format",
+            ));
+        }
+
+        // The first level has the full resolution.
+        let mut levels: Vec<Level> = self
+            .levels(dimensions)
+            .into_iter()
+            .enumerate()
+            .rev()
+            .map(|(number, size)| Level::new(number, size))
+            .collect();
+
+        let height = dimensions.height as usize;
+        let row_bytes = levels[0].row_bytes();
+        let mut band = vec![0u8; self.tile_size.min(height) * row_bytes];
+        let mut y = 0;
+        while y < height {
+            let rows = (band.len() / row_bytes).min(height - y);
+            let band = &mut band[..rows * row_bytes];
+            read_band(band, y, rows)?;
+            self.push_rows(&mut levels, band, on_tile)?;
+            y += rows;
+        }
+        self.finish(&mut levels, on_tile)
+    }
+
+    /// Appends `rows` to the first of `levels`, downsamples them into the levels below, and
+    /// emits all the tiles that are complete.
+    fn push_rows(
+        &self,
+        levels: &mut [Level],
+        rows: &[u8],
+        on_tile: &mut dyn FnMut(&Tile) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let (level, lower_levels) = levels.split_first_mut().unwrap();
+        let row_bytes = level.row_bytes();
+        level.pixels.extend_from_slice(rows);
+        level.rows_received += rows.len() / row_bytes;
+
+        if !lower_levels.is_empty() {
+            let mut downsampled = Vec::new();
+            for row in rows.chunks(row_bytes) {
+                match level.pending_row.take() {
+                    Some(previous) => downsample(&previous, row, &mut downsampled),
+                    None => level.pending_row = Some(row.to_vec()),
+                }
+            }
+            if !downsampled.is_empty() {
+                self.push_rows(lower_levels, &downsampled, on_tile)?;
+            }
+        }
+
+        self.emit_tiles(level, on_tile)
+    }
+
+    /// Downsamples the last row of levels with an odd height and emits the remaining tiles.
+    fn finish(
+        &self,
+        levels: &mut [Level],
+        on_tile: &mut dyn FnMut(&Tile) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let (level, lower_levels) = match levels.split_first_mut() {
+            Some(split) => split,
+            None => return Ok(()),
+        };
+        if let Some(row) = level.pending_row.take() {
+            let mut downsampled = Vec::new();
+            downsample(&row, &row, &mut downsampled);
+            self.push_rows(lower_levels, &downsampled, on_tile)?;
+        }
+        self.emit_tiles(level, on_tile)?;
+        self.finish(lower_levels, on_tile)
+    }
+
+    /// Emits all rows of tiles of `level` whose rows are received and drops the rows that are
+    /// not needed anymore.
+    fn emit_tiles(
+        &self,
+        level: &mut Level,
+        on_tile: &mut dyn FnMut(&Tile) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let tile_size = self.tile_size;
+        let overlap = self.overlap();
+        let row_bytes = level.row_bytes();
+        let columns = div_ceil(level.width, tile_size);
+        let rows = div_ceil(level.height, tile_size);
+
+        while level.next_tile_row < rows {
+            let row = level.next_tile_row;
+            let top = (row * tile_size).saturating_sub(overlap);
+            let bottom = ((row + 1) * tile_size + overlap).min(level.height);
+            if level.rows_received < bottom {
+                break;
+            }
+
+            for column in 0..columns {
+                let left = (column * tile_size).saturating_sub(overlap);
+                let right = ((column + 1) * tile_size + overlap).min(level.width);
+                let (tile_width, tile_height) = match self.layout {
+                    Layout::DeepZoom => (right - left, bottom - top),
+                    Layout::XYZ | Layout::TMS => (tile_size, tile_size),
+                };
+
+                let tile_row_bytes = tile_width * BYTES_PER_PIXEL;
+                let mut pixels = vec![0u8; tile_height * tile_row_bytes];
+                for y in top..bottom {
+                    let src = (y - level.first_row) * row_bytes + left * BYTES_PER_PIXEL;
+                    let dst = (y - top) * tile_row_bytes;
+                    let len = (right - left) * BYTES_PER_PIXEL;
+                    pixels[dst..dst + len].copy_from_slice(&level.pixels[src..src + len]);
+                }
+
+                let info = ImageInfo::new_n32_premul((tile_width as i32, tile_height as i32), None);
+                let data = Pixmap::new(&info, &pixels, tile_row_bytes)
+                    .encode(self.format, self.quality)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "failed to encode tile"))?;
+
+                let extension = self.extension().unwrap();
+                let (row, path) = match self.layout {
+                    Layout::DeepZoom => (
+                        row,
+                        format!("{}/{}_{}.{}", level.number, column, row, extension),
+                    ),
+                    Layout::XYZ => (
+                        row,
+                        format!("{}/{}/{}.{}", level.number, column, row, extension),
+                    ),
+                    Layout::TMS => {
+                        let row = (1 << level.number) - 1 - row;
+                        (
+                            row,
+                            format!("{}/{}/{}.{}", level.number, column, row, extension),
+                        )
+                    }
+                };
+
+                on_tile(&Tile {
+                    level: level.number,
+                    column,
+                    row,
+                    bounds: IRect::new(left as i32, top as i32, right as i32, bottom as i32),
+                    path,
+                    data: data.as_bytes(),
+                })?;
+            }
+
+            level.next_tile_row += 1;
+            let next_top = ((row + 1) * tile_size).saturating_sub(overlap);
+            let obsolete_rows = next_top.min(level.rows_received) - level.first_row;
+            level.pixels.drain(..obsolete_rows * row_bytes);
+            level.first_row += obsolete_rows;
+        }
+        Ok(())
+    }
+}
+
+/// The rows of a level that are currently kept in memory.
+struct Level {
+    number: usize,
+    width: usize,
+    height: usize,
+    /// The level row of the first row in `pixels`.
+    first_row: usize,
+    pixels: Vec<u8>,
+    rows_received: usize,
+    next_tile_row: usize,
+    /// A row that waits for the next row to be downsampled into the level below.
+    pending_row: Option<Vec<u8>>,
+}
+
+impl Level {
+    fn new(number: usize, size: ISize) -> Self {
+        Level {
+            number,
+            width: size.width as usize,
+            height: size.height as usize,
+            first_row: 0,
+            pixels: Vec::new(),
+            rows_received: 0,
+            next_tile_row: 0,
+            pending_row: None,
+        }
+    }
+
+    fn row_bytes(&self) -> usize {
+        self.width * BYTES_PER_PIXEL
+    }
+}
+
+/// Averages the 2x2 premultiplied pixels of the rows `a` and `b` and appends the resulting row
+/// to `dst`. The last pixel of a row with an odd width is averaged with itself.
+fn downsample(a: &[u8], b: &[u8], dst: &mut Vec<u8>) {
+    let width = a.len() / BYTES_PER_PIXEL;
+    for x in (0..width).step_by(2) {
+        let x0 = x * BYTES_PER_PIXEL;
+        let x1 = (x + 1).min(width - 1) * BYTES_PER_PIXEL;
+        for channel in 0..BYTES_PER_PIXEL {
+            let sum = a[x0 + channel] as u32
+                + a[x1 + channel] as u32
+                + b[x0 + channel] as u32
+                + b[x1 + channel] as u32;
+            dst.push(((sum + 2) / 4) as u8);
+        }
+    }
+}
+
+fn no_band_decoding() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "the codec supports neither top-down scanline nor subset decoding",
+    )
+}
+
+fn div_ceil(a: usize, b: usize) -> usize {
+    (a + b - 1) / b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Layout, TilePyramid};
+    use crate::{
+        Bitmap, Codec, Color, Data, EncodedImageFormat, IRect, ISize, Paint, PictureRecorder, Rect,
+    };
+
+    fn picture() -> crate::Picture {
+        let mut recorder = PictureRecorder::new();
+        let bounds = Rect::new(0.0, 0.0, 300.0, 200.0);
+        let mut paint = Paint::default();
+        paint.set_color(Color::RED);
+        recorder
+            .begin_recording(bounds, None)
+            .draw_rect(bounds, &paint);
+        recorder.finish_recording_as_picture(None).unwrap()
+    }
+
+    #[test]
+    fn deep_zoom_levels_and_tiles() {
+        let mut pyramid = TilePyramid::new();
+        pyramid.set_tile_size(128).set_overlap(1);
+
+        let levels = pyramid.levels((300, 200));
+        assert_eq!(levels.len(), 10);
+        assert_eq!(levels[0], ISize::new(1, 1));
+        assert_eq!(levels[8], ISize::new(150, 100));
+        assert_eq!(levels[9], ISize::new(300, 200));
+
+        let mut tiles = Vec::new();
+        pyramid
+            .build_from_picture(&picture(), (300, 200), |tile| {
+                tiles.push((tile.path.clone(), tile.bounds));
+                let image = crate::Image::from_encoded(Data::new_copy(tile.data)).unwrap();
+                assert_eq!(image.bounds().size(), tile.bounds.size());
+                Ok(())
+            })
+            .unwrap();
+
+        let top_level: Vec<_> = tiles
+            .iter()
+            .filter(|(path, _)| path.starts_with("9/"))
+            .collect();
+        assert_eq!(top_level.len(), 6);
+        assert!(tiles.contains(&("9/1_0.jpg".into(), IRect::new(127, 0, 257, 129))));
+        assert!(tiles.contains(&("9/2_1.jpg".into(), IRect::new(255, 127, 300, 200))));
+        assert!(tiles.contains(&("0/0_0.jpg".into(), IRect::new(0, 0, 1, 1))));
+        assert_eq!(tiles.len(), 6 + 2 + 8);
+    }
+
+    #[test]
+    fn tms_tiles_from_codec() {
+        let mut bitmap = Bitmap::new();
+        assert!(bitmap.try_alloc_n32_pixels((300, 200), false));
+        bitmap.erase_color(Color::BLUE);
+        let png = bitmap.encode(EncodedImageFormat::PNG, 100).unwrap();
+        let mut codec = Codec::from_data(png).unwrap();
+
+        let mut pyramid = TilePyramid::new();
+        pyramid
+            .set_layout(Layout::TMS)
+            .set_tile_size(256)
+            .set_format(EncodedImageFormat::PNG);
+        assert_eq!(pyramid.levels((300, 200)).len(), 2);
+
+        let mut paths = Vec::new();
+        pyramid
+            .build_from_codec(&mut codec, |tile| {
+                let mut codec = Codec::from_data(Data::new_copy(tile.data)).unwrap();
+                assert_eq!(codec.dimensions(), ISize::new(256, 256));
+                assert_eq!(codec.encoded_format(), EncodedImageFormat::PNG);
+                let _ = codec.get_image(None, None).unwrap();
+                paths.push(tile.path.clone());
+                Ok(())
+            })
+            .unwrap();
+        paths.sort();
+        assert_eq!(paths, ["0/0/0.png", "1/0/1.png", "1/1/1.png"]);
+    }
+
+    #[test]
+    #[cfg(feature = "webp-decode")]
+    fn tiles_from_codec_without_scanline_decoding() {
+        use super::div_ceil;
+        use crate::Contains;
+
+        // WebP codecs decode subsets, but not scanlines.
+        let webp = include_bytes!("../../tests/images/color_wheel.webp");
+        let mut codec = Codec::from_data(Data::new_copy(webp)).unwrap();
+
+        let mut pyramid = TilePyramid::new();
+        pyramid
+            .set_tile_size(64)
+            .set_format(EncodedImageFormat::PNG);
+        let levels = pyramid.levels(codec.dimensions());
+        let mut tiles = 0;
+        pyramid
+            .build_from_codec(&mut codec, |tile| {
+                let level = levels[tile.level];
+                assert!(IRect::from_size(level).contains(tile.bounds));
+                let image = crate::Image::from_encoded(Data::new_copy(tile.data)).unwrap();
+                assert_eq!(image.bounds().size(), tile.bounds.size());
+                tiles += 1;
+                Ok(())
+            })
+            .unwrap();
+        let expected: usize = levels
+            .iter()
+            .map(|level| div_ceil(level.width as usize, 64) * div_ceil(level.height as usize, 64))
+            .sum();
+        assert_eq!(tiles, expected);
+
+        // WebP subsets start at even rows.
+        pyramid.set_tile_size(63);
+        let error = pyramid
+            .build_from_codec(&mut codec, |_| Ok(()))
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn unsupported_format() {
+        let mut pyramid = TilePyramid::new();
+        pyramid.set_format(EncodedImageFormat::KTX);
+        let result = pyramid.build_from_picture(&picture(), (10, 10), |_| Ok(()));
+        assert!(result.is_err());
+    }
+}
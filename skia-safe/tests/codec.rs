@@ -1,5 +1,10 @@
 //! Tests for the various image encoder and decoders skia-safe supports by default.
-use skia_safe::{codec, Bitmap, Data, EncodedImageFormat};
+use skia_safe::{
+    codec, named_transfer_fn, AnimatedImage, Bitmap, ColorSpace, ColorType, Data,
+    EncodedImageFormat, IRect, ISize, ImageInfo, JpegDownsample, JpegEncoder, PngEncoder,
+    PngFilterFlags,
+};
+use std::io;
 
 /// The supported encoders.
 const STANDARD_ENCODERS: &[EncodedImageFormat] =
@@ -65,6 +70,274 @@ fn test_supported_decoders() {
     assert_eq!(supported, supported_decoders());
 }
 
+#[test]
+fn test_decode_from_stream() {
+    for (format, bytes) in DECODER_TESTS {
+        if !supported_decoders().contains(format) {
+            continue;
+        }
+        let from_data = codec::Codec::from_data(Data::new_copy(bytes)).unwrap();
+        let codec = codec::Codec::from_stream(io::Cursor::new(*bytes)).unwrap();
+        assert_eq!(codec.encoded_format(), *format);
+        assert_eq!(codec.dimensions(), from_data.dimensions());
+
+        let mut codec = codec::Codec::from_seekable_stream(io::Cursor::new(*bytes)).unwrap();
+        let info = codec.info();
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+        assert_eq!(
+            codec.get_pixels_with_options(&info, &mut pixels, row_bytes, None),
+            codec::Result::Success
+        );
+    }
+}
+
+/// A 1x1 GIF that shows a black and a white frame for 100ms each and loops forever.
+const ANIMATED_GIF: &[u8] = &[
+    // header, logical screen descriptor, and global color table
+    b'G', b'I', b'F', b'8', b'9', b'a', 1, 0, 1, 0, 0x80, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff,
+    // loop forever
+    0x21, 0xff, 0x0b, b'N', b'E', b'T', b'S', b'C', b'A', b'P', b'E', b'2', b'.', b'0', 3, 1, 0, 0,
+    0, // first frame
+    0x21, 0xf9, 4, 0, 10, 0, 0, 0, 0x2c, 0, 0, 0, 0, 1, 0, 1, 0, 0, 2, 2, 0x44, 0x01, 0,
+    // second frame
+    0x21, 0xf9, 4, 0, 10, 0, 0, 0, 0x2c, 0, 0, 0, 0, 1, 0, 1, 0, 0, 2, 2, 0x4c, 0x01, 0,
+    // trailer
+    0x3b,
+];
+
+#[test]
+fn test_frame_infos() {
+    let mut codec = codec::Codec::from_data(Data::new_copy(ANIMATED_GIF)).unwrap();
+    assert_eq!(codec.get_frame_count(), 2);
+    assert_eq!(codec.get_repetition_count(), None);
+
+    let infos = codec.frame_infos();
+    assert_eq!(infos.len(), 2);
+    for info in &infos {
+        assert_eq!(info.duration, 100);
+        assert!(info.fully_received);
+        assert_eq!(info.frame_rect, skia_safe::IRect::new(0, 0, 1, 1));
+    }
+    assert_eq!(infos[0].required_frame(), None);
+    assert_eq!(codec.frame_info(1), Some(infos[1]));
+    assert_eq!(codec.frame_info(2), None);
+}
+
+#[test]
+fn test_animated_image() {
+    let mut image = AnimatedImage::from_stream(io::Cursor::new(ANIMATED_GIF)).unwrap();
+    assert_eq!(image.frame_count(), 2);
+    assert_eq!(image.repetition_count(), None);
+    assert_eq!(image.current_frame_duration(), 100);
+
+    let first = image.current_frame().unwrap();
+    assert_eq!(image.decode_next_frame(), Some(100));
+    let second = image.current_frame().unwrap();
+    assert_eq!(first.dimensions(), second.dimensions());
+    assert_ne!(first.unique_id(), second.unique_id());
+
+    image.set_repetition_count(0);
+    assert_eq!(image.decode_next_frame(), None);
+    assert!(image.is_finished());
+    image.reset();
+    assert!(!image.is_finished());
+
+    let mut drawable = image.to_drawable();
+    let mut surface = skia_safe::Surface::new_raster_n32_premul((1, 1)).unwrap();
+    drawable.draw(surface.canvas(), None);
+}
+
+#[test]
+fn test_android_codec_sampled_decode() {
+    let data = Data::new_copy(include_bytes!("images/mandrill_16.png"));
+    let mut codec = codec::AndroidCodec::from_data(data).unwrap();
+    assert_eq!(codec.encoded_format(), EncodedImageFormat::PNG);
+    assert_eq!(codec.info().dimensions(), ISize::new(16, 16));
+    assert_eq!(codec.get_sampled_dimensions(2), ISize::new(8, 8));
+    assert_eq!(codec.compute_sample_size((4, 4)), (4, ISize::new(4, 4)));
+
+    let subset = IRect::new(0, 0, 8, 8);
+    assert_eq!(
+        codec.get_sampled_subset_dimensions(2, subset),
+        ISize::new(4, 4)
+    );
+
+    let color_type = codec.compute_output_color_type(ColorType::n32());
+    let alpha_type = codec.compute_output_alpha_type(false);
+    let info = ImageInfo::new((8, 8), color_type, alpha_type, None);
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+    let options = codec::AndroidOptions {
+        sample_size: 2,
+        ..Default::default()
+    };
+    assert_eq!(
+        codec.get_android_pixels(&info, &mut pixels, row_bytes, &options),
+        codec::Result::Success
+    );
+
+    let image = codec.get_android_image(ColorType::n32(), 4).unwrap();
+    assert_eq!(image.dimensions(), ISize::new(4, 4));
+}
+
+#[test]
+fn test_icc_profile_embedding() {
+    let mut bitmap = Bitmap::new();
+    assert!(bitmap.try_alloc_n32_pixels((16, 16), true));
+    let to_xyzd50 = ColorSpace::new_srgb().to_xyzd50().unwrap();
+    let color_space = ColorSpace::new_rgb(&named_transfer_fn::DOT22, &to_xyzd50).unwrap();
+
+    for format in supported_encoders() {
+        let encoded = bitmap
+            .encode_with_color_space(format, 100, color_space.clone())
+            .unwrap();
+        let codec = codec::Codec::from_data(encoded).unwrap();
+        let icc = codec.icc_profile().unwrap();
+        let embedded = ColorSpace::from_icc(icc.as_bytes()).unwrap();
+        assert!((embedded.transfer_fn().g - 2.2).abs() < 0.001);
+        assert!(embedded.to_xyzd50().is_some());
+
+        let encoded = bitmap.encode_with_color_space(format, 100, None).unwrap();
+        assert!(codec::Codec::from_data(encoded)
+            .unwrap()
+            .icc_profile()
+            .is_none());
+    }
+}
+
+#[test]
+fn test_format_encoders() {
+    let mut bitmap = Bitmap::new();
+    assert!(bitmap.try_alloc_n32_pixels((16, 16), true));
+    let pixmap = bitmap.pixmap();
+
+    let png = PngEncoder {
+        filter_flags: PngFilterFlags::NONE | PngFilterFlags::SUB,
+        zlib_level: 9,
+        comments: vec![("Title".into(), "Design asset".into())],
+    };
+    let encoded = png.encode(pixmap).unwrap();
+    let mut written = Vec::new();
+    png.encode_to_writer(pixmap, &mut written).unwrap();
+    assert_eq!(encoded.as_bytes(), &written[..]);
+    assert!(written.windows(5).any(|w| w == b"Title"));
+    let codec = codec::Codec::from_data(encoded).unwrap();
+    assert_eq!(codec.encoded_format(), EncodedImageFormat::PNG);
+
+    let clamped = PngEncoder {
+        zlib_level: 42,
+        ..png.clone()
+    };
+    assert_eq!(clamped.encode(pixmap).unwrap().as_bytes(), &written[..]);
+
+    let jpeg = JpegEncoder {
+        quality: 95,
+        downsample: JpegDownsample::Downsample444,
+        ..Default::default()
+    };
+    let mut written = Vec::new();
+    jpeg.encode_to_writer(pixmap, &mut written).unwrap();
+    let codec = codec::Codec::from_data(Data::new_copy(&written)).unwrap();
+    assert_eq!(codec.encoded_format(), EncodedImageFormat::JPEG);
+    assert_eq!(codec.dimensions(), ISize::new(16, 16));
+}
+
+#[test]
+fn test_png_comments_with_null_characters_are_rejected() {
+    let mut bitmap = Bitmap::new();
+    assert!(bitmap.try_alloc_n32_pixels((4, 4), true));
+    let png = PngEncoder {
+        comments: vec![("Title".into(), "nul\0byte".into())],
+        ..Default::default()
+    };
+    assert!(png.encode(bitmap.pixmap()).is_none());
+    let error = png
+        .encode_to_writer(bitmap.pixmap(), &mut Vec::new())
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_encoder_returns_writer_errors() {
+    struct Full;
+    impl io::Write for Full {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut bitmap = Bitmap::new();
+    assert!(bitmap.try_alloc_n32_pixels((4, 4), true));
+    let error = PngEncoder::default()
+        .encode_to_writer(bitmap.pixmap(), &mut Full)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "disk full");
+}
+
+#[test]
+fn test_row_encoder() {
+    let mut bitmap = Bitmap::new();
+    assert!(bitmap.try_alloc_n32_pixels((16, 16), false));
+    bitmap.erase_color(skia_safe::Color::RED);
+    let pixmap = bitmap.pixmap();
+    let png = PngEncoder::default();
+
+    let mut written = Vec::new();
+    let mut encoder = png.row_encoder(pixmap.info(), &mut written).unwrap();
+    for band in 0..4 {
+        let rows = pixmap
+            .extract_subset(IRect::from_xywh(0, band * 4, 16, 4))
+            .unwrap();
+        encoder.encode_rows(&rows).unwrap();
+    }
+    assert!(encoder.is_finished());
+    let too_many_rows = pixmap
+        .extract_subset(IRect::from_xywh(0, 0, 16, 1))
+        .unwrap();
+    assert!(encoder.encode_rows(&too_many_rows).is_err());
+    drop(encoder);
+    assert_eq!(&written[..], png.encode(pixmap).unwrap().as_bytes());
+
+    let mut written = Vec::new();
+    let mut encoder = JpegEncoder::default()
+        .row_encoder(pixmap.info(), &mut written)
+        .unwrap();
+    encoder.encode_rows(pixmap).unwrap();
+    drop(encoder);
+    let codec = codec::Codec::from_data(Data::new_copy(&written)).unwrap();
+    assert_eq!(codec.dimensions(), ISize::new(16, 16));
+}
+
+#[cfg(feature = "webp-encode")]
+#[test]
+fn test_webp_lossless_encoder() {
+    use skia_safe::{WebpCompression, WebpEncoder};
+
+    let mut bitmap = Bitmap::new();
+    assert!(bitmap.try_alloc_n32_pixels((16, 16), false));
+    bitmap.erase_color(skia_safe::Color::from_argb(0x80, 0x10, 0x20, 0x30));
+    let webp = WebpEncoder {
+        compression: WebpCompression::Lossless,
+        quality: 100.0,
+    };
+    let encoded = webp.encode(bitmap.pixmap()).unwrap();
+    let image = skia_safe::Image::from_encoded(encoded).unwrap();
+    let info = ImageInfo::new_n32((1, 1), skia_safe::AlphaType::Unpremul, None);
+    let mut pixel = [0u8; 4];
+    assert!(image.read_pixels(
+        &info,
+        &mut pixel,
+        4,
+        (0, 0),
+        skia_safe::image::CachingHint::Allow
+    ));
+    assert_eq!(pixel[3], 0x80);
+}
+
 type DecoderTest = (EncodedImageFormat, &'static [u8]);
 
 // image files copied from skia/resources/images
@@ -17,16 +17,22 @@ fn sendable_implements_send() {
 }
 
 mod codec {
-    use skia_safe::{codec, Codec};
+    use skia_safe::{codec, AnimatedImage, Codec};
     use static_assertions::*;
 
     // Codec seems to call into SkPngChunkReader*
     assert_not_impl_any!(Codec: Send, Sync);
+    assert_not_impl_any!(AnimatedImage: Send, Sync);
+    assert_not_impl_any!(codec::AndroidCodec: Send, Sync);
+    assert_impl_all!(codec::AndroidOptions: Send, Sync);
 
     assert_impl_all!(codec::Result: Send, Sync);
     assert_impl_all!(codec::SelectionPolicy: Send, Sync);
     assert_impl_all!(codec::ZeroInitialized: Send, Sync);
     assert_impl_all!(codec::ScanlineOrder: Send, Sync);
+    assert_impl_all!(codec::FrameInfo: Send, Sync);
+    assert_impl_all!(codec::DisposalMethod: Send, Sync);
+    assert_impl_all!(codec::Blend: Send, Sync);
 }
 
 mod core {
@@ -66,12 +72,18 @@ mod core {
     assert_impl_all!(Image: Send, Sync);
     assert_impl_all!(image::CubicResampler: Send, Sync);
     assert_impl_all!(image::BitDepth: Send, Sync);
+    assert_impl_all!(image::AsyncReadResult: Send, Sync);
 
     assert_impl_all!(ImageFilter: Send, Sync);
     assert_impl_all!(ImageGenerator: Send, Sync);
     assert_impl_all!(ColorInfo: Send, Sync);
     assert_impl_all!(ImageInfo: Send, Sync);
+    assert_impl_all!(JpegEncoder: Send, Sync);
     assert_impl_all!(M44: Send, Sync);
+    assert_impl_all!(PngEncoder: Send, Sync);
+    assert_not_impl_any!(RowEncoder<'static>: Send, Sync);
+    #[cfg(feature = "webp-encode")]
+    assert_impl_all!(WebpEncoder: Send, Sync);
     assert_impl_all!(MaskFilter: Send, Sync);
     assert_impl_all!(Matrix: Send, Sync);
     assert_impl_all!(Paint: Send, Sync);
@@ -143,6 +155,7 @@ mod effects {
     assert_impl_all!(runtime_effect::Varying: Send, Sync);
     assert_not_impl_any!(RuntimeEffect: Send, Sync);
     assert_impl_all!(runtime_effect::Options: Send, Sync);
+    assert_impl_all!(runtime_effect::CompileError: Send, Sync);
     assert_impl_all!(image_filters::CropRect: Send, Sync);
     assert_impl_all!(image_filters::Dither: Send, Sync);
 }
@@ -268,6 +281,35 @@ mod svg {
     use skia_safe::svg::*;
     use static_assertions::*;
     assert_not_impl_any!(Canvas: Send, Sync);
+    #[cfg(feature = "svg")]
+    assert_not_impl_any!(Dom: Send, Sync);
+}
+
+#[cfg(feature = "animation")]
+mod skottie {
+    use skia_safe::skottie::*;
+    use static_assertions::*;
+    assert_not_impl_any!(Animation: Send, Sync);
+    assert_impl_all!(ColorPropertyHandle: Send);
+    assert_not_impl_any!(ColorPropertyHandle: Sync);
+    assert_impl_all!(OpacityPropertyHandle: Send);
+    assert_not_impl_any!(OpacityPropertyHandle: Sync);
+    assert_not_impl_any!(NativePropertyObserver: Send, Sync);
+}
+
+#[cfg(feature = "particles")]
+mod particles {
+    use skia_safe::particles::*;
+    use static_assertions::*;
+    assert_not_impl_any!(EffectParams: Send, Sync);
+    assert_not_impl_any!(Effect: Send, Sync);
+}
+
+#[cfg(any(feature = "svg", feature = "animation", feature = "particles"))]
+mod resources {
+    use skia_safe::resources::*;
+    use static_assertions::*;
+    assert_not_impl_any!(NativeResourceProvider: Send, Sync);
 }
 
 mod utils {